@@ -0,0 +1,506 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+use ink_lang as ink;
+//cargo +nightly test
+#[ink::contract(version = "0.1.0")]
+mod auction_factory {
+    use auction::Auction;
+    use ink_core::{
+        env::call::{CallParams, Selector},
+        storage,
+    };
+    use ink_lang::ToAccountId;
+    use ink_prelude::vec::Vec;
+
+    /// Most entries returned by a single paginated query
+    const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Denominator for amounts given in basis points, i.e. 10_000 is 100%
+    const BASIS_POINTS: u32 = 10_000;
+
+    /// Deploys or calls another contract like the auction contract's `cross_contract` does, returning None if that
+    /// failed. Tests answer from a queue
+    #[cfg(not(test))]
+    fn cross_contract<R: scale::Decode, E>(call: impl FnOnce() -> Result<R, E>) -> Option<R> {
+        call().ok()
+    }
+
+    #[cfg(test)]
+    fn cross_contract<R: scale::Decode, E>(_call: impl FnOnce() -> Result<R, E>) -> Option<R> {
+        tests::next_answer()
+    }
+
+    /// Whether a deployed auction's first lot is still open
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum Status {
+        Active,
+        Ended,
+    }
+
+    /// An auction deployed by the factory
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct AuctionRecord {
+        auction: AccountId,
+        creator: AccountId,
+        created_time: Timestamp,
+        status: Status,
+    }
+
+    /// Defines the storage of your contract.
+    /// Add new fields to the below struct in order
+    /// to add new static storage fields to your contract.
+    #[ink(storage)]
+    struct AuctionFactory {
+        owner: storage::Value<AccountId>, // account that can change the code hash and the defaults
+        auction_code_hash: storage::Value<Hash>, // code of the auction contract new auctions are instantiated from
+        fee_recipient: storage::Value<AccountId>, // gets the platform fee of every new auction
+        fee_basis_points: storage::Value<u32>, // platform fee of every new auction, 0 for none
+        min_increment: storage::Value<Balance>, // minimum increment of every new auction
+        records: storage::HashMap<u32, AuctionRecord>, // every auction deployed, in order
+        record_count: storage::Value<u32>, // number of auctions deployed
+        active: storage::HashMap<u32, u32>, // indices of the records of active auctions, in no particular order
+        active_count: storage::Value<u32>, // number of active auctions
+        active_position: storage::HashMap<u32, u32>, // position of each active record in `active`
+        ended: storage::HashMap<u32, u32>, // indices of the records of ended auctions, in the order they were seen to end
+        ended_count: storage::Value<u32>, // number of ended auctions
+    }
+
+
+    // events
+    #[ink(event)]
+    struct Auction_Deployed {
+        #[ink(topic)]
+        index: u32,
+        #[ink(topic)]
+        auction: Option<AccountId>,
+        #[ink(topic)]
+        creator: Option<AccountId>,
+        created_time: Timestamp,
+    }
+
+    #[ink(event)]
+    struct Deployment_Failed {
+        #[ink(topic)]
+        creator: Option<AccountId>,
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    struct Auction_Ended {
+        #[ink(topic)]
+        index: u32,
+        #[ink(topic)]
+        auction: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Defaults_Changed {
+        #[ink(topic)]
+        fee_recipient: Option<AccountId>,
+        #[ink(topic)]
+        fee_basis_points: u32,
+        #[ink(topic)]
+        min_increment: Balance,
+    }
+
+    #[ink(event)]
+    struct Invalid_Fee {
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        fee_basis_points: u32,
+    }
+
+    #[ink(event)]
+    struct Code_Hash_Changed {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    struct Not_Owner {
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        owner: Option<AccountId>,
+    }
+
+
+    impl AuctionFactory {
+        /// Constructor that sets up a factory deploying auctions from the uploaded code `auction_code_hash`. Every
+        /// new auction requires bids to beat the highest bid by `min_increment` and pays `fee_basis_points` of its
+        /// proceeds to `fee_recipient`, which can't exceed 10000 basis points. The caller becomes the owner who can
+        /// change these later
+        #[ink(constructor)]
+        fn new(&mut self, auction_code_hash: Hash, fee_recipient: AccountId, fee_basis_points: u32,
+               min_increment: Balance) {
+            assert!(fee_basis_points <= BASIS_POINTS, "fee exceeds 10000 basis points");
+            self.owner.set(self.env().caller());
+            self.auction_code_hash.set(auction_code_hash);
+            self.fee_recipient.set(fee_recipient);
+            self.fee_basis_points.set(fee_basis_points);
+            self.min_increment.set(min_increment);
+            self.record_count.set(0);
+            self.active_count.set(0);
+            self.ended_count.set(0);
+        }
+
+        /// Deploys an auction like `Auction::new(init_value, millisecs)` with the caller as beneficiary and the
+        /// factory's defaults, and returns its account. The money sent along endows the new contract. Returns None
+        /// and sends the money back if the contract could not be instantiated
+        #[ink(message)]
+        fn create_auction(&mut self, init_value: Balance, millisecs: u64) -> Option<AccountId> {
+            let creator = self.env().caller();
+            let endowment: Balance = self.env().transferred_balance();
+            let deployment = Auction::new_for(creator, init_value, millisecs, *self.min_increment,
+                                              *self.fee_recipient, *self.fee_basis_points)
+                .endowment(endowment)
+                .using_code(*self.auction_code_hash);
+            let deployed = cross_contract(|| {
+                deployment.create_using(self.env()).map(|auction| auction.to_account_id())
+            });
+            let auction: AccountId = match deployed {
+                Some(auction) => auction,
+                None => {
+                    if endowment > 0 {
+                        assert!(self.env().transfer(creator, endowment).is_ok(), "cannot return endowment");
+                    }
+                    self.env().emit_event(Deployment_Failed {
+                        creator: Some(creator),
+                        code_hash: *self.auction_code_hash,
+                    });
+                    return None
+                }
+            };
+
+            let index = *self.record_count;
+            let created_time = self.env().block_timestamp();
+            self.records.insert(index, AuctionRecord {
+                auction,
+                creator,
+                created_time,
+                status: Status::Active,
+            });
+            self.record_count.set(index + 1);
+            let position = *self.active_count;
+            self.active.insert(position, index);
+            self.active_position.insert(index, position);
+            self.active_count.set(position + 1);
+
+            self.env().emit_event(Auction_Deployed {
+                index,
+                auction: Some(auction),
+                creator: Some(creator),
+                created_time,
+            });
+            Some(auction)
+        }
+
+        /// Asks the auction at `index` whether its first lot has ended and moves it to the ended auctions if so,
+        /// anyone can call it. Returns whether the auction is ended
+        #[ink(message)]
+        fn refresh_status(&mut self, index: u32) -> bool {
+            let mut record = match self.get_auction(index) {
+                Some(record) => record,
+                None => return false,
+            };
+            if record.status == Status::Ended {
+                return true
+            }
+            let call = CallParams::<EnvTypes, bool>::eval(record.auction, Selector::from_str("is_ended"))
+                .push_arg(&0u32);
+            if !cross_contract(|| call.fire()).unwrap_or(false) {
+                return false
+            }
+
+            record.status = Status::Ended;
+            self.records.insert(index, record);
+
+            // swap the last active record into the position of this one
+            let position = *self.active_position.get(&index).unwrap_or(&0);
+            let last = *self.active_count - 1;
+            let last_index = *self.active.get(&last).unwrap_or(&index);
+            self.active.insert(position, last_index);
+            self.active_position.insert(last_index, position);
+            self.active.remove(&last);
+            self.active_position.remove(&index);
+            self.active_count.set(last);
+
+            let ended_position = *self.ended_count;
+            self.ended.insert(ended_position, index);
+            self.ended_count.set(ended_position + 1);
+
+            self.env().emit_event(Auction_Ended {
+                index,
+                auction: Some(record.auction),
+            });
+            true
+        }
+
+        /// lets the owner change the defaults passed to auctions deployed from now on, the fee can't exceed
+        /// 10000 basis points
+        #[ink(message)]
+        fn set_defaults(&mut self, fee_recipient: AccountId, fee_basis_points: u32, min_increment: Balance) -> bool {
+            if !self.is_owner() {
+                return false
+            }
+            else if fee_basis_points > BASIS_POINTS {
+                self.env().emit_event(Invalid_Fee {
+                    sender: Some(self.env().caller()),
+                    fee_basis_points,
+                });
+                return false
+            }
+            self.fee_recipient.set(fee_recipient);
+            self.fee_basis_points.set(fee_basis_points);
+            self.min_increment.set(min_increment);
+            self.env().emit_event(Defaults_Changed {
+                fee_recipient: Some(fee_recipient),
+                fee_basis_points,
+                min_increment,
+            });
+            true
+        }
+
+        /// lets the owner point the factory at newly uploaded auction code
+        #[ink(message)]
+        fn set_auction_code_hash(&mut self, code_hash: Hash) -> bool {
+            if !self.is_owner() {
+                return false
+            }
+            self.auction_code_hash.set(code_hash);
+            self.env().emit_event(Code_Hash_Changed {
+                code_hash,
+            });
+            true
+        }
+
+        /// returns whether the caller is the owner, emitting `Not_Owner` if not
+        fn is_owner(&self) -> bool {
+            if self.env().caller() != *self.owner {
+                self.env().emit_event(Not_Owner {
+                    sender: Some(self.env().caller()),
+                    owner: Some(*self.owner),
+                });
+                return false
+            }
+            true
+        }
+
+        /// returns the factory's owner
+        #[ink(message)]
+        fn get_owner(&self) -> AccountId {
+            *self.owner
+        }
+
+        /// returns the code hash new auctions are instantiated from
+        #[ink(message)]
+        fn get_auction_code_hash(&self) -> Hash {
+            *self.auction_code_hash
+        }
+
+        /// returns the account that gets the platform fee of new auctions
+        #[ink(message)]
+        fn get_fee_recipient(&self) -> AccountId {
+            *self.fee_recipient
+        }
+
+        /// returns the platform fee of new auctions in basis points
+        #[ink(message)]
+        fn get_fee_basis_points(&self) -> u32 {
+            *self.fee_basis_points
+        }
+
+        /// returns the minimum increment of new auctions
+        #[ink(message)]
+        fn get_min_increment(&self) -> Balance {
+            *self.min_increment
+        }
+
+        /// returns the number of auctions deployed
+        #[ink(message)]
+        fn get_auction_count(&self) -> u32 {
+            *self.record_count
+        }
+
+        /// returns the auction deployed at position `index`
+        #[ink(message)]
+        fn get_auction(&self, index: u32) -> Option<AuctionRecord> {
+            self.records.get(&index).cloned()
+        }
+
+        /// returns up to `limit` (at most 100) auctions starting at position `start`, in the order they were deployed
+        #[ink(message)]
+        fn get_auctions(&self, start: u32, limit: u32) -> Vec<AuctionRecord> {
+            let end = core::cmp::min(*self.record_count, start.saturating_add(core::cmp::min(limit, MAX_PAGE_SIZE)));
+            (start..end)
+                .filter_map(|index| self.get_auction(index))
+                .collect()
+        }
+
+        /// returns the number of auctions not yet seen to have ended
+        #[ink(message)]
+        fn get_active_count(&self) -> u32 {
+            *self.active_count
+        }
+
+        /// returns up to `limit` (at most 100) active auctions starting at position `start`. The order changes as
+        /// auctions end
+        #[ink(message)]
+        fn get_active_auctions(&self, start: u32, limit: u32) -> Vec<AuctionRecord> {
+            let end = core::cmp::min(*self.active_count, start.saturating_add(core::cmp::min(limit, MAX_PAGE_SIZE)));
+            (start..end)
+                .filter_map(|position| self.active.get(&position).and_then(|index| self.get_auction(*index)))
+                .collect()
+        }
+
+        /// returns the number of auctions seen to have ended with `refresh_status`
+        #[ink(message)]
+        fn get_ended_count(&self) -> u32 {
+            *self.ended_count
+        }
+
+        /// returns up to `limit` (at most 100) ended auctions starting at position `start`, in the order they
+        /// were seen to end
+        #[ink(message)]
+        fn get_ended_auctions(&self, start: u32, limit: u32) -> Vec<AuctionRecord> {
+            let end = core::cmp::min(*self.ended_count, start.saturating_add(core::cmp::min(limit, MAX_PAGE_SIZE)));
+            (start..end)
+                .filter_map(|position| self.ended.get(&position).and_then(|index| self.get_auction(*index)))
+                .collect()
+        }
+    }
+
+    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
+    /// module and test functions are marked with a `#[test]` attribute.
+    /// The below code is technically just normal Rust code.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ink_core::env;
+        use std::cell::RefCell;
+
+        thread_local! {
+            // encoded outcomes of the next deployments and calls, None for one that fails, oldest first
+            static ANSWERS: RefCell<Vec<Option<Vec<u8>>>> = RefCell::new(Vec::new());
+        }
+
+        // queues the outcomes of the next deployments and calls: the deployed account or the value returned
+        fn mock_answers<T: scale::Encode>(answers: &[Option<T>]) {
+            ANSWERS.with(|queue| {
+                queue.borrow_mut().extend(answers.iter().map(|answer| answer.as_ref().map(scale::Encode::encode)))
+            });
+        }
+
+        // decodes the outcome of the next deployment or call
+        pub(super) fn next_answer<R: scale::Decode>() -> Option<R> {
+            let answer = ANSWERS.with(|queue| {
+                let mut queue = queue.borrow_mut();
+                assert!(!queue.is_empty(), "unexpected cross-contract call");
+                queue.remove(0)
+            });
+            answer.and_then(|encoded| scale::Decode::decode(&mut &encoded[..]).ok())
+        }
+
+        // makes the following calls come from `sender`
+        fn set_sender(sender: AccountId) {
+            let callee = env::account_id::<EnvTypes>().unwrap_or([0x0; 32].into());
+            env::test::push_execution_context::<EnvTypes>(
+                sender,
+                callee,
+                1000000,
+                0,
+                env::test::CallData::new(env::call::Selector::from_str("")),
+            );
+        }
+
+        /// We test if the constructor does its job.
+        #[test]
+        fn new_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let factory = AuctionFactory::new(Hash::from([1; 32]), accounts.django, 250, 5);
+            assert_eq!(factory.get_owner(), accounts.alice);
+            assert_eq!(factory.get_auction_code_hash(), Hash::from([1; 32]));
+            assert_eq!(factory.get_fee_recipient(), accounts.django);
+            assert_eq!(factory.get_fee_basis_points(), 250);
+            assert_eq!(factory.get_min_increment(), 5);
+            assert_eq!(factory.get_auction_count(), 0);
+            assert_eq!(factory.get_auctions(0, 10), Vec::new());
+            assert_eq!(factory.get_active_auctions(0, 10), Vec::new());
+            assert_eq!(factory.get_ended_auctions(0, 10), Vec::new());
+            assert!(factory.get_auction(0).is_none());
+        }
+
+        // we test that only the owner can change the defaults
+        #[test]
+        fn set_defaults_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut factory = AuctionFactory::new(Hash::from([1; 32]), accounts.django, 250, 5);
+
+            set_sender(accounts.bob);
+            assert!(!factory.set_defaults(accounts.bob, 0, 0));
+            assert!(!factory.set_auction_code_hash(Hash::from([2; 32])));
+
+            set_sender(accounts.alice);
+            assert!(!factory.set_defaults(accounts.eve, 10_001, 1));
+            assert_eq!(factory.get_fee_basis_points(), 250);
+            assert!(factory.set_defaults(accounts.eve, 100, 1));
+            assert!(factory.set_auction_code_hash(Hash::from([2; 32])));
+            assert_eq!(factory.get_fee_recipient(), accounts.eve);
+            assert_eq!(factory.get_fee_basis_points(), 100);
+            assert_eq!(factory.get_min_increment(), 1);
+            assert_eq!(factory.get_auction_code_hash(), Hash::from([2; 32]));
+        }
+
+        // we test that a fee above 10000 basis points is refused up front
+        #[test]
+        #[should_panic(expected = "fee exceeds 10000 basis points")]
+        fn new_refuses_excessive_fee() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            AuctionFactory::new(Hash::from([1; 32]), accounts.django, 10_001, 5);
+        }
+
+        // we test that deployed auctions are recorded and move to the ended auctions once they end
+        #[test]
+        fn create_auction_and_refresh_status_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut factory = AuctionFactory::new(Hash::from([1; 32]), accounts.django, 250, 5);
+            mock_answers(&[None, Some(accounts.eve), Some(accounts.frank)]);
+
+            set_sender(accounts.bob);
+            assert_eq!(factory.create_auction(0, 60), None);
+            assert_eq!(factory.get_auction_count(), 0);
+            assert_eq!(factory.create_auction(0, 60), Some(accounts.eve));
+            set_sender(accounts.charlie);
+            assert_eq!(factory.create_auction(0, 60), Some(accounts.frank));
+
+            let first = factory.get_auction(0).expect("first auction is recorded");
+            let second = factory.get_auction(1).expect("second auction is recorded");
+            assert_eq!((first.auction, first.creator, first.status), (accounts.eve, accounts.bob, Status::Active));
+            assert_eq!((second.auction, second.creator), (accounts.frank, accounts.charlie));
+            assert_eq!(factory.get_auctions(0, 10), vec![first, second]);
+            assert_eq!(factory.get_active_count(), 2);
+
+            // the first auction has not ended yet or can't be asked
+            mock_answers(&[Some(false), None]);
+            assert!(!factory.refresh_status(0));
+            assert!(!factory.refresh_status(0));
+            assert!(!factory.refresh_status(2));
+            mock_answers(&[Some(true)]);
+            assert!(factory.refresh_status(0));
+            assert!(factory.refresh_status(0));
+
+            assert_eq!(factory.get_auction(0).map(|record| record.status), Some(Status::Ended));
+            assert_eq!(factory.get_active_count(), 1);
+            assert_eq!(factory.get_active_auctions(0, 10), vec![second]);
+            assert_eq!(factory.get_ended_count(), 1);
+            assert_eq!(factory.get_ended_auctions(0, 10).len(), 1);
+            assert_eq!(factory.get_ended_auctions(0, 10)[0].auction, accounts.eve);
+        }
+    }
+}
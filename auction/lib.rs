@@ -1,66 +1,476 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use ink_lang as ink;
-//cargo +nightly test 
+//cargo +nightly test
 #[ink::contract(version = "0.1.0")]
 mod auction {
-    use ink_core::storage;
+    use ink_core::{
+        env::call::{CallParams, Selector},
+        storage,
+    };
+    use ink_prelude::{
+        string::String,
+        vec::Vec,
+    };
+
+    /// Identifies a single lot hosted by the auction house.
+    type AuctionId = u32;
+
+    /// Denominator for amounts given in basis points, i.e. 10_000 is 100%
+    const BASIS_POINTS: u32 = 10_000;
+
+    /// Most entries returned by a single paginated query
+    const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Most slots the ending period of a candle lot is split into
+    const MAX_CANDLE_SLOTS: u32 = 256;
+
+    /// What the `transfer` and `transfer_from` messages of the asset and token contracts return: `Ok(())` on
+    /// success, otherwise an error whose variant index is all we decode
+    type TransferResult = Result<(), u8>;
+
+    /// Makes a call to another contract and returns its decoded answer, None if the call or the decoding failed.
+    /// The off-chain environment can't reach other contracts, so under test the answers come from a queue instead
+    #[cfg(not(test))]
+    fn cross_contract<R: scale::Decode, E>(call: impl FnOnce() -> Result<R, E>) -> Option<R> {
+        call().ok()
+    }
+
+    #[cfg(test)]
+    fn cross_contract<R: scale::Decode, E>(_call: impl FnOnce() -> Result<R, E>) -> Option<R> {
+        tests::next_answer()
+    }
+
+    /// The bidding rules a lot is run with
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum AuctionKind {
+        /// open ascending bids, every bid is visible as soon as it is made
+        English,
+        /// bidders commit to a hash of (amount, salt) with a deposit, then reveal after end_time
+        Sealed,
+        /// descending price, the first bid that covers the current asking price wins immediately
+        Dutch,
+        /// identical units are sold to the highest unit prices, all winners pay the lowest winning unit price
+        MultiUnit,
+        /// open ascending bids, the lot closes at a random point of its ending period drawn at settlement
+        Candle,
+    }
+
+    /// How much a new bid has to beat the highest bid by
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum Increment {
+        /// a fixed amount
+        Absolute(Balance),
+        /// basis points of the highest bid
+        BasisPoints(u32),
+    }
+
+    /// An asset held by another contract that is escrowed by a lot and handed over at settlement. The contract's
+    /// `transfer` and `transfer_from` messages must return a `Result<(), Error>` like ink's ERC721 and ERC20
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum Asset {
+        /// the token `token_id` of an ERC721/PSP34-style `contract`
+        Nft { contract: AccountId, token_id: u32 },
+        /// `amount` of an ERC20/PSP22-style `contract`'s token
+        Fungible { contract: AccountId, amount: Balance },
+    }
+
+    /// What happened to a bid recorded in a lot's bid history
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum BidStatus {
+        /// became the highest bid
+        Accepted,
+        /// became the highest bid, placed by a proxy on the bidder's behalf
+        Automatic,
+        /// won the lot instantly at its buy-now or dutch asking price
+        Bought,
+        LowerThanStartingPrice,
+        LowerThanHighestBid,
+        BelowMinIncrement,
+        LowerThanAskingPrice,
+        /// bidding on the lot had not opened yet
+        NotStarted,
+        /// the lot had already ended or its bidding window was over
+        Ended,
+        /// the lot does not take this kind of bid
+        WrongKind,
+        /// the bidder is not eligible to bid on the lot
+        NotEligible,
+        /// the bidder had not registered the lot's bid bond
+        NoBond,
+        /// the revealed sealed bid was more than the bidder's deposit
+        ExceedsDeposit,
+    }
+
+    /// A single entry of a lot's bid history
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct BidRecord {
+        bidder: AccountId,
+        amount: Balance,
+        time: Timestamp,
+        status: BidStatus,
+    }
+
+    /// A bid on a multi-unit lot, kept in a list ordered by unit price
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct UnitBid {
+        bidder: AccountId,
+        quantity: u32,
+        unit_price: Balance,
+        /// the next bid of the list, at a lower unit price or placed later at the same one
+        next: Option<u32>,
+    }
+
+    /// How far the settlement of a multi-unit lot has got
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum SettlementPhase {
+        /// walking the bids to find the lowest winning unit price
+        Pricing,
+        /// walking the bids again to pay out winners and refund losers
+        Paying,
+        Done,
+    }
+
+    /// The state of a multi-unit lot's settlement, which is done in batches by `settle_units`
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct UnitSettlement {
+        phase: SettlementPhase,
+        /// the next bid to visit
+        cursor: Option<u32>,
+        /// units handed out to the bids visited so far in the current phase
+        units_filled: u32,
+        /// units sold, final once the pricing phase is over
+        units_sold: u32,
+        /// unit price of the lowest winning bid, final once the pricing phase is over
+        clearing_price: Balance,
+    }
+
+    /// How the asking price of a dutch lot falls from its starting price to its floor price
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum PriceDecay {
+        /// the price falls continuously between created_time and end_time
+        Linear,
+        /// the price falls like `Linear` but only once every given number of milliseconds (or blocks)
+        Step(Timestamp),
+    }
+
+    /// Why a call to one of the bidding messages, `end` or `withdraw` failed
+    #[derive(scale::Encode, scale::Decode, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum AuctionError {
+        NoSuchAuction,
+        /// the lot does not take this kind of bid
+        WrongAuctionKind,
+        /// the lot is ended or its bidding window is over
+        BiddingClosed,
+        NotStarted,
+        NotEligible,
+        /// the lot requires a bid bond the bidder has not registered
+        BondRequired,
+        /// the lot does not take bid bonds
+        NoBondRequired,
+        /// the lot is not in the phase the call belongs to
+        WrongPhase,
+        /// the quantity is 0 or more than the lot's units
+        InvalidQuantity,
+        /// the caller is not the lot's highest bidder
+        NotHighestBidder,
+        /// the lot has no purchase awaiting confirmation
+        NotAwaitingConfirmation,
+        LowerThanStartingPrice,
+        LowerThanHighestBid,
+        BelowMinIncrement,
+        LowerThanAskingPrice,
+        /// native balance was sent to a house that takes a payment token
+        WrongCurrency,
+        TokenTransferFailed,
+        TransferFailed,
+        InsufficientPendingReturns,
+        AlreadyEnded,
+        /// the caller may not end the lot yet
+        EndTooEarly,
+        NothingToWithdraw,
+    }
+
+    /// What happens to the money sent with a bid that is rejected
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum RejectionPolicy {
+        /// it is added to the bidder's withdraw balance
+        CreditPending,
+        /// it is sent straight back to the bidder
+        Revert,
+    }
+
+    /// A share of every lot's proceeds paid to someone other than its beneficiary
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct Split {
+        recipient: AccountId,
+        basis_points: u32,
+    }
+
+    /// Who may bid on a lot
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum Eligibility {
+        /// anyone
+        Open,
+        /// the accounts the beneficiary added to the lot's allowlist
+        Allowlist,
+        /// the accounts that proved with `prove_eligibility` that they are a leaf of the Merkle tree with this root
+        MerkleRoot(Hash),
+    }
+
+    /// When the beneficiary of a lot may cancel it
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum CancelPolicy {
+        /// only while no bid has reached the starting price
+        BeforeBids,
+        /// at any time before the lot ends, paying the highest bidder the given basis points of their bid
+        WithPenalty(u32),
+    }
+
+    /// What the winner of a lot pays at settlement
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum PriceRule {
+        /// the winner pays their own bid
+        FirstPrice,
+        /// the winner pays the runner-up's bid, or the starting price if there is no runner-up (Vickrey)
+        SecondPrice,
+    }
+
+    /// What the lots' deadlines and windows are measured in. Every time, duration and window a house takes or
+    /// returns, such as the 'millisecs' arguments, is in milliseconds, or blocks in a block-measured house
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum TimeUnit {
+        /// block timestamps, in milliseconds
+        Milliseconds,
+        /// block numbers
+        Blocks,
+    }
+
+    /// Where the winning bid of an escrowed lot stands after the lot is ended
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    enum EscrowState {
+        /// held by the contract until the winner confirms delivery or the timeout passes
+        AwaitingDelivery,
+        /// held by the contract until the arbitrator splits it
+        Disputed,
+        /// paid out to the beneficiary
+        Released,
+        /// split by the arbitrator between the winner and the beneficiary
+        Resolved,
+    }
+
+    /// What is being sold in a lot
+    #[derive(scale::Encode, scale::Decode, storage::Flush, Clone, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct Metadata {
+        title: String,
+        /// content hash of the lot's description, such as an IPFS CID
+        content_hash: String,
+        category: String,
+    }
+
+    /// Summary of a lot returned by `get_info`
+    #[derive(scale::Encode, scale::Decode, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    #[cfg_attr(feature = "std", derive(Debug))]
+    struct LotInfo {
+        kind: AuctionKind,
+        beneficiary: AccountId,
+        proposed_beneficiary: Option<AccountId>,
+        metadata: Metadata,
+        starting_price: Balance,
+        highest_bid: Balance,
+        highest_bidder: AccountId,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        is_ended: bool,
+    }
 
     /// Defines the storage of your contract.
     /// Add new fields to the below struct in order
     /// to add new static storage fields to your contract.
     #[ink(storage)]
     struct Auction {
-        beneficiary: storage::Value<AccountId>, // this is the account that created the auction
-        highest_bidder: storage::Value<AccountId>, //highest bidder
-        highest_bid: storage::Value<Balance>, // highest bid amount (Balance can only be positive)
-        starting_price: storage::Value<Balance>, // starting bid amount (Balance can only be positive)
-        ended: storage::Value<bool>, // is the auction over
-        pending_returns: storage::HashMap<AccountId, Balance>, // Allowed withdrawals of previous bids
-        created_time: storage::Value<Timestamp>, //time the auction was created
-        end_time: storage::Value<Timestamp>, //time the auction is set to be allowed to end
+        next_auction_id: storage::Value<AuctionId>, // id that will be given to the next lot created
+        min_increment: storage::Value<Increment>, // how much a new bid has to beat the highest bid by on every lot
+        payment_token: storage::Value<Option<AccountId>>, // ERC20/PSP22-style token bids and withdrawals are made in, native balance if None
+        platform_fee: storage::Value<Option<Split>>, // share of every lot's proceeds taken by the house
+        royalties: storage::HashMap<u32, Split>, // further shares of every lot's proceeds, fixed at construction
+        royalty_count: storage::Value<u32>, // number of royalty splits
+        rejection_policy: storage::Value<RejectionPolicy>, // what happens to the money sent with a rejected bid
+        time_unit: storage::Value<TimeUnit>, // whether times are block timestamps or block numbers
+        beneficiary: storage::HashMap<AuctionId, AccountId>, // this is the account that created the lot
+        highest_bidder: storage::HashMap<AuctionId, AccountId>, //highest bidder per lot
+        highest_bid: storage::HashMap<AuctionId, Balance>, // highest bid amount per lot (Balance can only be positive)
+        starting_price: storage::HashMap<AuctionId, Balance>, // starting bid amount per lot (Balance can only be positive)
+        ended: storage::HashMap<AuctionId, bool>, // is the lot's auction over
+        cancelled: storage::HashMap<AuctionId, bool>, // was the lot's auction called off by its beneficiary, cancelled lots are ended too
+        cancel_policy: storage::HashMap<AuctionId, CancelPolicy>, // when the beneficiary may cancel the lot
+        eligibility: storage::HashMap<AuctionId, Eligibility>, // who may bid on the lot
+        allowlist: storage::HashMap<(AuctionId, AccountId), bool>, // accounts the beneficiary allowed to bid on the lot
+        proven: storage::HashMap<(AuctionId, AccountId), Hash>, // Merkle root each account proved membership of for the lot
+        units: storage::HashMap<AuctionId, u32>, // multi-unit lots: number of units for sale
+        unit_bids: storage::HashMap<(AuctionId, u32), UnitBid>, // multi-unit lots: bids by index, linked from highest to lowest unit price
+        unit_bid_count: storage::HashMap<AuctionId, u32>, // multi-unit lots: number of bids placed
+        unit_bid_head: storage::HashMap<AuctionId, u32>, // multi-unit lots: index of the bid with the highest unit price
+        unit_settlement: storage::HashMap<AuctionId, UnitSettlement>, // multi-unit lots: progress of the settlement once ended
+        units_won: storage::HashMap<(AuctionId, AccountId), u32>, // multi-unit lots: units each account won
+        candle_slots: storage::HashMap<AuctionId, u32>, // candle lots: number of slots in the ending period that ends at end_time
+        candle_slot_length: storage::HashMap<AuctionId, Timestamp>, // candle lots: length of a slot
+        candle_leaders: storage::HashMap<(AuctionId, u32), AccountId>, // candle lots: leader at the end of each slot a bid was made in, slot 0 being before the ending period
+        candle_bids: storage::HashMap<(AuctionId, u32), Balance>, // candle lots: leading bid at the end of each such slot
+        candle_locked: storage::HashMap<(AuctionId, AccountId), Balance>, // candle lots: each bidder's latest bid, locked until the lot is settled
+        candle_close: storage::HashMap<AuctionId, u32>, // candle lots: slot drawn as the close, once settled
+        candle_end_block: storage::HashMap<AuctionId, BlockNumber>, // candle lots: block the lot was ended in, the close is drawn in the next one
+        bid_bond: storage::HashMap<AuctionId, Balance>, // bond an account has to register before bidding on the lot
+        confirm_window: storage::HashMap<AuctionId, Timestamp>, // bonded lots: time the winner has to confirm the purchase
+        bonds: storage::HashMap<(AuctionId, AccountId), Balance>, // bonded lots: bond registered by each account
+        runner_up: storage::HashMap<AuctionId, AccountId>, // account whose bid is the second highest bid
+        confirm_deadline: storage::HashMap<AuctionId, Timestamp>, // bonded lots: deadline of the purchase awaiting confirmation
+        offer_unpaid: storage::HashMap<AuctionId, bool>, // bonded lots: whether the highest bid still has to be paid, as after an offer to the runner-up
+        escrow_arbitrator: storage::HashMap<AuctionId, AccountId>, // escrowed lots: account that settles disputes
+        escrow_timeout: storage::HashMap<AuctionId, Timestamp>, // escrowed lots: time the winner has to confirm delivery
+        dispute_timeout: storage::HashMap<AuctionId, Timestamp>, // escrowed lots: time the arbitrator has to settle a dispute
+        default_winner_share: storage::HashMap<AuctionId, u32>, // escrowed lots: basis points the winner gets if the arbitrator doesn't act
+        escrow_state: storage::HashMap<AuctionId, EscrowState>, // escrowed lots: where the winning bid stands once ended
+        escrow_deadline: storage::HashMap<AuctionId, Timestamp>, // escrowed lots: time after which the winning bid can be released, or the dispute resolved by default
+        escrowed_amount: storage::HashMap<AuctionId, Balance>, // escrowed lots: price held by the contract
+        proposed_beneficiary: storage::HashMap<AuctionId, AccountId>, // account the beneficiary offered the lot to
+        metadata: storage::HashMap<AuctionId, Metadata>, // what is being sold in the lot
+        pending_returns: storage::HashMap<AccountId, Balance>, // Allowed withdrawals of previous bids across all lots
+        created_time: storage::HashMap<AuctionId, Timestamp>, //time the lot was created
+        start_time: storage::HashMap<AuctionId, Timestamp>, //time bidding opens on the lot, if later than created_time
+        end_time: storage::HashMap<AuctionId, Timestamp>, //time the lot is set to be allowed to end
+        kind: storage::HashMap<AuctionId, AuctionKind>, // bidding rules of the lot
+        reveal_end_time: storage::HashMap<AuctionId, Timestamp>, // sealed lots: end of the reveal phase that follows end_time
+        unrevealed_penalty: storage::HashMap<AuctionId, u32>, // sealed lots: basis points of the deposit forfeited when a bid is never revealed
+        commitments: storage::HashMap<(AuctionId, AccountId), Hash>, // sealed lots: hash of (amount, salt) committed by each bidder
+        deposits: storage::HashMap<(AuctionId, AccountId), Balance>, // sealed lots: deposit locked with each commitment
+        commitment_count: storage::HashMap<AuctionId, u32>, // sealed lots: number of accounts that committed to a bid
+        price_rule: storage::HashMap<AuctionId, PriceRule>, // what the winner of the lot pays at settlement
+        second_highest_bid: storage::HashMap<AuctionId, Balance>, // best valid bid of the lot after the highest one
+        floor_price: storage::HashMap<AuctionId, Balance>, // dutch lots: asking price reached at end_time
+        price_decay: storage::HashMap<AuctionId, PriceDecay>, // dutch lots: how the asking price falls to the floor price
+        extension_window: storage::HashMap<AuctionId, Timestamp>, // bids this close to end_time extend the lot
+        extension_length: storage::HashMap<AuctionId, Timestamp>, // how far such a bid pushes end_time out
+        max_extension: storage::HashMap<AuctionId, Timestamp>, // cap on the total time end_time can be pushed out
+        total_extension: storage::HashMap<AuctionId, Timestamp>, // time end_time has been pushed out so far
+        reserve_price: storage::HashMap<AuctionId, Balance>, // lowest highest bid the lot is sold for, never returned by a getter
+        reserve_commitment: storage::HashMap<AuctionId, Hash>, // hash of (reserve, salt) while a hidden reserve is unrevealed
+        buy_now_price: storage::HashMap<AuctionId, Balance>, // bid that wins the lot instantly
+        buy_now_threshold: storage::HashMap<AuctionId, u32>, // basis points of the buy-now price the highest bid may reach before buying now is withdrawn
+        proxy_max: storage::HashMap<AuctionId, Balance>, // maximum escrowed by the highest bidder's proxy bid, 0 for a manual bid
+        asset: storage::HashMap<AuctionId, Asset>, // asset escrowed by the lot and delivered at settlement
+        asset_delivered: storage::HashMap<AuctionId, bool>, // whether the escrowed asset has left the contract
+        bid_history: storage::HashMap<(AuctionId, u32), BidRecord>, // append-only log of the bids made on each lot
+        bid_count: storage::HashMap<AuctionId, u32>, // length of each lot's bid history
+        account_bids: storage::HashMap<(AuctionId, AccountId, u32), u32>, // positions of an account's bids in a lot's bid history
+        account_bid_count: storage::HashMap<(AuctionId, AccountId), u32>, // number of bids an account made on a lot
     }
 
 
     // events
     #[ink(event)]
     struct Created {
+        #[ink(topic)]
+        auction_id: AuctionId,
         #[ink(topic)]
         beneficiary: Option<AccountId>,
         #[ink(topic)]
         starting_bid: Balance,
-        #[ink(topic)]
         created_time: Timestamp,
+        start_time: Timestamp,
         #[ink(topic)]
         end_time: Timestamp,
+        time_unit: TimeUnit,
     }
-    
+
 
     #[ink(event)]
     struct New_Highest_Bid {
         #[ink(topic)]
-        previous_highest_bidder: Option<AccountId>,
+        auction_id: AuctionId,
         #[ink(topic)]
+        previous_highest_bidder: Option<AccountId>,
         previous_highest_bid: Balance,
         #[ink(topic)]
         highest_bidder: Option<AccountId>,
         #[ink(topic)]
         highest_bid: Balance,
+        automatic: bool, // whether the bid was placed by a proxy on behalf of the bidder
+    }
+
+    #[ink(event)]
+    struct Proxy_Max_Raised {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
     }
 
     #[ink(event)]
     struct Failed_Bid_Lower_Than_Highest_Bid {
+        #[ink(topic)]
+        auction_id: AuctionId,
         #[ink(topic)]
         attempted_bidder: Option<AccountId>,
         #[ink(topic)]
         attempted_bid: Balance,
-        #[ink(topic)]
         highest_bidder: Option<AccountId>,
         #[ink(topic)]
         highest_bid: Balance,
     }
 
+    #[ink(event)]
+    struct Failed_Bid_Below_Min_Increment {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        attempted_bidder: Option<AccountId>,
+        #[ink(topic)]
+        attempted_bid: Balance,
+        #[ink(topic)]
+        min_bid: Balance,
+    }
+
     #[ink(event)]
     struct Failed_Bid_Lower_Than_Starting_Price {
+        #[ink(topic)]
+        auction_id: AuctionId,
         #[ink(topic)]
         attempted_bidder: Option<AccountId>,
         #[ink(topic)]
@@ -69,6 +479,46 @@ mod auction {
         starting_price: Balance,
     }
 
+    #[ink(event)]
+    struct Failed_Bid_Lower_Than_Asking_Price {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        attempted_bidder: Option<AccountId>,
+        #[ink(topic)]
+        attempted_bid: Balance,
+        #[ink(topic)]
+        asking_price: Balance,
+    }
+
+    #[ink(event)]
+    struct Insufficient_Pending_Returns {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        requested: Balance,
+        #[ink(topic)]
+        available: Balance,
+    }
+
+    #[ink(event)]
+    struct Wrong_Currency {
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        payment_token: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Token_Transfer_Failed {
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        amount: Balance,
+    }
+
     #[ink(event)]
     struct Withdrawal {
         #[ink(topic)]
@@ -80,6 +530,8 @@ mod auction {
 
     #[ink(event)]
     struct Ended {
+        #[ink(topic)]
+        auction_id: AuctionId,
         #[ink(topic)]
         highest_bidder: Option<AccountId>,
         #[ink(topic)]
@@ -87,304 +539,3660 @@ mod auction {
     }
 
     #[ink(event)]
-    struct Already_Ended {
+    struct Settled {
         #[ink(topic)]
-        highest_bidder: Option<AccountId>,
+        auction_id: AuctionId,
         #[ink(topic)]
-        highest_bid: Balance,
+        winner: Option<AccountId>,
+        #[ink(topic)]
+        winning_bid: Balance,
+        #[ink(topic)]
+        clearing_price: Balance,
     }
 
     #[ink(event)]
-    struct No_More_Bidding {
+    struct Payout {
         #[ink(topic)]
-        is_ended: bool,
+        auction_id: AuctionId,
         #[ink(topic)]
-        highest_bidder: Option<AccountId>,
+        recipient: Option<AccountId>,
         #[ink(topic)]
-        highest_bid: Balance,
+        amount: Balance,
     }
 
+    #[ink(event)]
+    struct Bought_Now {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        buyer: Option<AccountId>,
+        #[ink(topic)]
+        price: Balance,
+    }
 
     #[ink(event)]
-    struct Not_Beneficiary {
+    struct Invalid_Buy_Now_Price {
         #[ink(topic)]
-        sender: Option<AccountId>,
+        auction_id: AuctionId,
         #[ink(topic)]
-        beneficiary: Option<AccountId>,
+        price: Balance,
+        #[ink(topic)]
+        starting_price: Balance,
     }
 
+    #[ink(event)]
+    struct Asset_Escrowed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        beneficiary: Option<AccountId>,
+        asset: Asset,
+    }
 
-    impl Auction {
-        /// Constructor that initializes the starting_price value to the given `init_value`
-        /// and the amount of time (in milliseconds) until non-beneficiaries can bid using 'millisecs'
-        #[ink(constructor)]
-        fn new(&mut self, init_value: Balance, millisecs: u64) {
-            self.beneficiary.set(self.env().caller());
-            self.highest_bidder.set(self.env().caller());
-            self.starting_price.set(init_value);
-            self.highest_bid.set(0);
-            self.ended.set(false);
-            self.pending_returns.insert(self.env().caller(), 0);
+    #[ink(event)]
+    struct Asset_Delivered {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        recipient: Option<AccountId>,
+        asset: Asset,
+    }
 
+    #[ink(event)]
+    struct Asset_Transfer_Failed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        recipient: Option<AccountId>,
+        asset: Asset,
+    }
 
-            // Timestamps are in milliseconds
-            let curr_time: Timestamp = self.env().block_timestamp();
-            self.created_time.set(curr_time);
-            self.end_time.set(curr_time.saturating_add(millisecs));
+    #[ink(event)]
+    struct Reserve_Not_Met {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        highest_bidder: Option<AccountId>,
+        #[ink(topic)]
+        highest_bid: Balance,
+    }
 
-            // emit event
-            self.env().emit_event(Created {
-                beneficiary: Some(self.env().caller()),
-                starting_bid: init_value,
-                created_time: curr_time,
-                end_time: curr_time.saturating_add(millisecs),
-            });
-        }
+    #[ink(event)]
+    struct Reserve_Revealed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        reserve_price: Balance,
+    }
 
+    #[ink(event)]
+    struct Cancelled {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        highest_bidder: Option<AccountId>,
+        #[ink(topic)]
+        refunded: Balance,
+        penalty: Balance,
+    }
 
-        /// Constructors can delegate to other constructors.
-        #[ink(constructor)]
-        fn default(&mut self) {
-            self.new(0, 60)
-        }
+    #[ink(event)]
+    struct Cancel_Not_Allowed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        policy: CancelPolicy,
+        #[ink(topic)]
+        highest_bid: Balance,
+    }
 
-        /// returns the highestBid.
-        fn get_highest_bid(&self) -> Balance {
-            *self.highest_bid
-        }
+    #[ink(event)]
+    struct Not_Eligible {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        eligibility: Eligibility,
+    }
 
-        /// returns the starting_price.
-        #[ink(message)]
-        fn get_starting_price(&self) -> Balance {
-            *self.starting_price
-        }
+    #[ink(event)]
+    struct Unit_Bid_Placed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        quantity: u32,
+        #[ink(topic)]
+        unit_price: Balance,
+    }
 
-        /// returns the balance in the contract
-        #[ink(message)]
-        fn get_contract_balance(&self) -> Balance {
-            self.env().balance()
-        }
+    #[ink(event)]
+    struct Invalid_Quantity {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        quantity: u32,
+        units: u32,
+    }
 
-        /// returns the highest asking price so far i.e max(highest_bid, starting_price)
-        #[ink(message)]
-        fn get_current_asking_price(&self) -> Balance {
-            if self.get_highest_bid() > self.get_starting_price() {
-                return self.get_highest_bid()
-            }
-            self.get_starting_price()
-        }
+    #[ink(event)]
+    struct Units_Won {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        quantity: u32,
+        #[ink(topic)]
+        unit_price: Balance,
+    }
 
-        /// returns the highestBidder.
-        #[ink(message)]
-        fn get_highest_bidder(&self) -> AccountId {
-            *self.highest_bidder
-        }
+    #[ink(event)]
+    struct Units_Settled {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        units_sold: u32,
+        #[ink(topic)]
+        clearing_price: Balance,
+    }
 
-        /// returns the AccountId of the beneficiary
-        #[ink(message)]
-        fn get_beneficiary(&self) -> AccountId {
-            * self.beneficiary
-        }
+    #[ink(event)]
+    struct Candle_Closed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        close_slot: u32,
+        #[ink(topic)]
+        winner: Option<AccountId>,
+        winning_bid: Balance,
+    }
 
-        /// returns whether the auction ended
-        #[ink(message)]
-        fn is_ended(&self) -> bool {
-            *self.ended
-        }
+    #[ink(event)]
+    struct Candle_Ended {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        end_block: BlockNumber,
+    }
 
-        /// returns the sender's withdrawl balance
-        #[ink(message)]
-        fn my_withdrawl_balance(&self) -> Balance {
-            *self.pending_returns.get(&self.env().caller()).unwrap_or(&0)
-        }
+    #[ink(event)]
+    struct Candle_Bid_Released {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        amount: Balance,
+    }
 
-        /// returns a given accounts withdraw balance  
-         #[ink(message)]
-        fn curr_withdrawl_amount(&self, id: AccountId) -> Balance {
-            *self.pending_returns.get(&id).unwrap_or(&0)
-        }
+    #[ink(event)]
+    struct Bond_Registered {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        bond: Balance,
+    }
 
-        /// returns whether a non-beneficiaries can end the auction
-        #[ink(message)]
-        fn time_end_allowed(&self) -> bool{
-            self.env().block_timestamp() > *self.end_time
-        }
+    #[ink(event)]
+    struct Bond_Required {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        bond: Balance,
+    }
 
-        /// returns the current block timestamp
-        #[ink(message)]
-        fn get_time(&self) -> Timestamp{
-            self.env().block_timestamp()
+    #[ink(event)]
+    struct Bond_Released {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        bond: Balance,
+    }
+
+    #[ink(event)]
+    struct Bond_Forfeited {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        bond: Balance,
+    }
+
+    #[ink(event)]
+    struct Awaiting_Confirmation {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        winner: Option<AccountId>,
+        #[ink(topic)]
+        price: Balance,
+        deadline: Timestamp,
+    }
+
+    #[ink(event)]
+    struct Purchase_Confirmed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        winner: Option<AccountId>,
+        #[ink(topic)]
+        price: Balance,
+    }
+
+    #[ink(event)]
+    struct Escrow_Held {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        winner: Option<AccountId>,
+        #[ink(topic)]
+        amount: Balance,
+        deadline: Timestamp,
+    }
+
+    #[ink(event)]
+    struct Escrow_Released {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    struct Dispute_Raised {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        raised_by: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Dispute_Resolved {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        winner_amount: Balance,
+        #[ink(topic)]
+        beneficiary_amount: Balance,
+    }
+
+    #[ink(event)]
+    struct Invalid_Arbitrator {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        arbitrator: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Wrong_Escrow_State {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        state: Option<EscrowState>,
+    }
+
+    #[ink(event)]
+    struct Beneficiary_Proposed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        beneficiary: Option<AccountId>,
+        #[ink(topic)]
+        proposed: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Beneficiary_Transferred {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        previous: Option<AccountId>,
+        #[ink(topic)]
+        beneficiary: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Metadata_Set {
+        #[ink(topic)]
+        auction_id: AuctionId,
+    }
+
+    #[ink(event)]
+    struct Already_Ended {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        highest_bidder: Option<AccountId>,
+        #[ink(topic)]
+        highest_bid: Balance,
+    }
+
+    #[ink(event)]
+    struct Not_Started {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        start_time: Timestamp,
+    }
+
+    #[ink(event)]
+    struct No_More_Bidding {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        is_ended: bool,
+        #[ink(topic)]
+        highest_bidder: Option<AccountId>,
+        highest_bid: Balance,
+    }
+
+
+    #[ink(event)]
+    struct Not_Beneficiary {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        beneficiary: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Sealed_Phases {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        commit_end_time: Timestamp,
+        #[ink(topic)]
+        reveal_end_time: Timestamp,
+        #[ink(topic)]
+        unrevealed_penalty: u32,
+    }
+
+    #[ink(event)]
+    struct Bid_Committed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        deposit: Balance,
+    }
+
+    #[ink(event)]
+    struct Bid_Revealed {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        amount: Balance,
+        #[ink(topic)]
+        leading: bool,
+    }
+
+    #[ink(event)]
+    struct Invalid_Reveal {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    struct Reveal_Exceeds_Deposit {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        amount: Balance,
+        deposit: Balance,
+    }
+
+    #[ink(event)]
+    struct Deposit_Forfeited {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        forfeited: Balance,
+        #[ink(topic)]
+        returned: Balance,
+    }
+
+    #[ink(event)]
+    struct Wrong_Auction_Kind {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        kind: AuctionKind,
+    }
+
+    #[ink(event)]
+    struct Wrong_Phase {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+        #[ink(topic)]
+        time: Timestamp,
+    }
+
+    #[ink(event)]
+    struct Auction_Extended {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        bidder: Option<AccountId>,
+        #[ink(topic)]
+        end_time: Timestamp,
+        #[ink(topic)]
+        total_extension: Timestamp,
+    }
+
+    #[ink(event)]
+    struct Config_Locked {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        highest_bid: Balance,
+    }
+
+    #[ink(event)]
+    struct No_Such_Auction {
+        #[ink(topic)]
+        auction_id: AuctionId,
+        #[ink(topic)]
+        sender: Option<AccountId>,
+    }
+
+
+    impl Auction {
+        /// Constructor that opens the auction house with a first lot (id 0) whose starting_price is the given
+        /// `init_value` and the amount of time (in milliseconds, or blocks) until non-beneficiaries can end it using
+        /// 'millisecs'
+        #[ink(constructor)]
+        fn new(&mut self, init_value: Balance, millisecs: u64) {
+            self.new_house(Increment::Absolute(1), None);
+            self.create_auction(init_value, millisecs);
+        }
+
+        /// Constructor like `new` whose first lot only opens for bidding at the time `start_time`,
+        /// non-beneficiaries can end it 'millisecs' milliseconds (or blocks) after that
+        #[ink(constructor)]
+        fn new_scheduled(&mut self, init_value: Balance, start_time: Timestamp, millisecs: u64) {
+            self.new_house(Increment::Absolute(1), None);
+            self.create_scheduled_auction(init_value, start_time, millisecs);
+        }
+
+        /// Constructor like `new` for an auction house whose bids and withdrawals are made in the ERC20/PSP22-style
+        /// `token` instead of native balance
+        #[ink(constructor)]
+        fn new_with_token(&mut self, token: AccountId, init_value: Balance, millisecs: u64) {
+            self.new_house(Increment::Absolute(1), Some(token));
+            self.create_auction(init_value, millisecs);
+        }
+
+        /// Constructor like `new` whose first lot is sold on behalf of `beneficiary` instead of the caller, so that
+        /// the auction factory or a multisig can deploy it for them. A new bid has to beat the highest bid by at
+        /// least `min_increment` and `fee_basis_points` of the proceeds go to `fee_recipient`
+        #[ink(constructor)]
+        fn new_for(&mut self, beneficiary: AccountId, init_value: Balance, millisecs: u64, min_increment: Balance,
+                   fee_recipient: AccountId, fee_basis_points: u32) {
+            let platform_fee = if fee_basis_points == 0 {
+                None
+            } else {
+                Some(Split { recipient: fee_recipient, basis_points: fee_basis_points })
+            };
+            self.new_marketplace(Increment::Absolute(min_increment), None, platform_fee, Vec::new(),
+                                 RejectionPolicy::CreditPending);
+            self.create_lot(AuctionKind::English, beneficiary, init_value, self.get_time(), millisecs);
+        }
+
+        /// Constructor like `new` for an auction house whose times are block numbers instead of block timestamps,
+        /// for chains whose timestamps drift. Non-beneficiaries can end the first lot `blocks` blocks after it was
+        /// created, and every window given to the house's messages is a number of blocks
+        #[ink(constructor)]
+        fn new_in_blocks(&mut self, init_value: Balance, blocks: u64) {
+            self.new_house(Increment::Absolute(1), None);
+            self.time_unit.set(TimeUnit::Blocks);
+            self.create_auction(init_value, blocks);
+        }
+
+        /// Constructor that opens an empty auction house, lots are added with `create_auction`.
+        /// A new bid has to beat the highest bid by at least `min_increment`, and always by at least 1.
+        /// If a `payment_token` is given bids are pulled from that ERC20/PSP22-style contract with the `*_tokens`
+        /// messages and withdrawals are paid in it, the payable messages then refuse native balance
+        #[ink(constructor)]
+        fn new_house(&mut self, min_increment: Increment, payment_token: Option<AccountId>) {
+            self.new_marketplace(min_increment, payment_token, None, Vec::new(), RejectionPolicy::CreditPending);
+        }
+
+        /// Constructor like `new_house` whose lots' proceeds are split at settlement: `platform_fee` and every one
+        /// of `royalties` take their basis points of the price paid, rounded down, and the beneficiary gets the rest.
+        /// The splits can't be changed later and together can't exceed 10000 basis points. `rejection_policy` says
+        /// whether money sent with a rejected bid is added to the bidder's withdraw balance or sent back
+        #[ink(constructor)]
+        fn new_marketplace(&mut self, min_increment: Increment, payment_token: Option<AccountId>,
+                           platform_fee: Option<Split>, royalties: Vec<Split>, rejection_policy: RejectionPolicy) {
+            let total = royalties.iter().chain(platform_fee.iter())
+                .fold(0u64, |total, split| total + u64::from(split.basis_points));
+            assert!(total <= u64::from(BASIS_POINTS), "splits exceed 10000 basis points");
+
+            self.next_auction_id.set(0);
+            self.min_increment.set(min_increment);
+            self.payment_token.set(payment_token);
+            self.platform_fee.set(platform_fee);
+            self.rejection_policy.set(rejection_policy);
+            self.time_unit.set(TimeUnit::Milliseconds);
+            self.royalty_count.set(royalties.len() as u32);
+            for (index, royalty) in royalties.into_iter().enumerate() {
+                self.royalties.insert(index as u32, royalty);
+            }
+            self.pending_returns.insert(self.env().caller(), 0);
+        }
+
+
+        /// Constructors can delegate to other constructors.
+        #[ink(constructor)]
+        fn default(&mut self) {
+            self.new(0, 60)
+        }
+
+        /// creates a new lot with the caller as beneficiary and returns its id. The lot's starting_price is
+        /// `init_value` and non-beneficiaries can end it after 'millisecs' milliseconds (or blocks)
+        #[ink(message)]
+        fn create_auction(&mut self, init_value: Balance, millisecs: u64) -> AuctionId {
+            self.create_lot(AuctionKind::English, self.env().caller(), init_value, self.get_time(), millisecs)
+        }
+
+        /// creates a new lot like `create_auction` that can be announced ahead of time: bidding only opens at the
+        /// time `start_time` and non-beneficiaries can end it 'millisecs' milliseconds (or blocks) after that
+        #[ink(message)]
+        fn create_scheduled_auction(&mut self, init_value: Balance, start_time: Timestamp, millisecs: u64) -> AuctionId {
+            self.create_lot(AuctionKind::English, self.env().caller(), init_value, start_time, millisecs)
+        }
+
+        /// creates a new sealed-bid lot with the caller as beneficiary and returns its id. Bidders commit during the
+        /// first 'commit_millisecs' milliseconds (or blocks) and reveal during the following 'reveal_millisecs'
+        /// milliseconds (or blocks), after which anyone can end the lot. Bidders that never reveal forfeit
+        /// `unrevealed_penalty` basis points of their deposit to the beneficiary. `price_rule` decides whether the
+        /// winner pays their own bid or the runner-up's
+        #[ink(message)]
+        fn create_sealed_auction(&mut self, init_value: Balance, commit_millisecs: u64,
+                                 reveal_millisecs: u64, unrevealed_penalty: u32, price_rule: PriceRule) -> AuctionId {
+            let auction_id = self.create_lot(AuctionKind::Sealed, self.env().caller(), init_value, self.get_time(), commit_millisecs);
+            self.price_rule.insert(auction_id, price_rule);
+            let reveal_end_time = self.get_end_time(auction_id).saturating_add(reveal_millisecs);
+            let unrevealed_penalty = core::cmp::min(unrevealed_penalty, BASIS_POINTS);
+            self.reveal_end_time.insert(auction_id, reveal_end_time);
+            self.unrevealed_penalty.insert(auction_id, unrevealed_penalty);
+
+            // emit event
+            self.env().emit_event(Sealed_Phases {
+                auction_id,
+                commit_end_time: self.get_end_time(auction_id),
+                reveal_end_time,
+                unrevealed_penalty,
+            });
+            auction_id
+        }
+
+        /// creates a new dutch lot with the caller as beneficiary and returns its id. The asking price starts at
+        /// `init_value` and falls to `floor_price` over 'millisecs' milliseconds (or blocks) following `price_decay`,
+        /// the first bid covering it wins. Once the time is up the lot can be ended unsold by anyone
+        #[ink(message)]
+        fn create_dutch_auction(&mut self, init_value: Balance, floor_price: Balance, millisecs: u64,
+                                price_decay: PriceDecay) -> AuctionId {
+            let auction_id = self.create_lot(AuctionKind::Dutch, self.env().caller(), init_value, self.get_time(), millisecs);
+            self.floor_price.insert(auction_id, core::cmp::min(floor_price, init_value));
+            self.price_decay.insert(auction_id, price_decay);
+            auction_id
+        }
+
+        /// creates a new multi-unit lot of `units` identical units with the caller as beneficiary and returns its id.
+        /// Bids name a quantity and a unit price above `init_value`, once the lot is ended the units go to the highest
+        /// unit prices (bids at the same price in the order they were placed, the last winner possibly only partly
+        /// filled) and every winner pays the lowest winning unit price. The lot is settled in batches with `settle_units`
+        #[ink(message)]
+        fn create_multi_unit_auction(&mut self, init_value: Balance, units: u32, millisecs: u64) -> AuctionId {
+            let auction_id = self.create_lot(AuctionKind::MultiUnit, self.env().caller(), init_value, self.get_time(), millisecs);
+            self.units.insert(auction_id, units);
+            auction_id
+        }
+
+        /// creates a new candle lot with the caller as beneficiary and returns its id. Bids work like on an english
+        /// lot until end_time, 'millisecs' milliseconds (or blocks) from now, but the last 'ending_period'
+        /// milliseconds (or blocks) are split into at most 256 slots of 'slot_length' milliseconds (or blocks) and
+        /// the leader at the end of every slot is remembered. Once the lot is ended one slot is drawn from
+        /// the chain's randomness with `draw_candle` in the next block and its leader wins with the bid they led with
+        /// then, so a last moment bid only counts if the draw falls late. Outbid bids stay locked until the close is
+        /// drawn and are then returned with `release_candle_bid`
+        #[ink(message)]
+        fn create_candle_auction(&mut self, init_value: Balance, millisecs: u64, ending_period: u64,
+                                 slot_length: u64) -> AuctionId {
+            let auction_id = self.create_lot(AuctionKind::Candle, self.env().caller(), init_value, self.get_time(), millisecs);
+            let slots = if slot_length == 0 {
+                0
+            } else {
+                core::cmp::min(core::cmp::min(ending_period, millisecs) / slot_length, u64::from(MAX_CANDLE_SLOTS)) as u32
+            };
+            self.candle_slots.insert(auction_id, slots);
+            self.candle_slot_length.insert(auction_id, slot_length);
+            auction_id
+        }
+
+        /// lets the beneficiary of an english lot protect it from sniping before the first bid: a successful bid made
+        /// within 'window' milliseconds (or blocks) of end_time pushes end_time out by 'length' milliseconds (or
+        /// blocks), for at most 'max_total' milliseconds (or blocks) over the life of the lot
+        #[ink(message)]
+        fn set_anti_sniping(&mut self, auction_id: AuctionId, window: u64, length: u64, max_total: u64) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+            else if self.get_kind(auction_id) != AuctionKind::English {
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    kind: self.get_kind(auction_id),
+                });
+                return false
+            }
+
+            self.extension_window.insert(auction_id, window);
+            self.extension_length.insert(auction_id, length);
+            self.max_extension.insert(auction_id, max_total);
+            true
+        }
+
+        /// lets the beneficiary set a reserve price before the first bid, if the highest bid is below it when the
+        /// lot is ended the bid is returned to the bidder instead of going to the beneficiary. Getters never return
+        /// the reserve, but it can be read from the contract's raw storage; use `commit_reserve_price` to keep it secret
+        #[ink(message)]
+        fn set_reserve_price(&mut self, auction_id: AuctionId, reserve_price: Balance) -> bool {
+            if !self.can_configure(auction_id) || !self.takes_reserve(auction_id) {
+                return false
+            }
+            self.reserve_commitment.remove(&auction_id);
+            self.reserve_price.insert(auction_id, reserve_price);
+            true
+        }
+
+        /// lets the beneficiary commit to a hidden reserve price before the first bid, `commitment` being the hash
+        /// returned by `compute_commitment(reserve_price, salt)`. The reserve counts as not met until it is revealed
+        /// with `reveal_reserve_price`
+        #[ink(message)]
+        fn commit_reserve_price(&mut self, auction_id: AuctionId, commitment: Hash) -> bool {
+            if !self.can_configure(auction_id) || !self.takes_reserve(auction_id) {
+                return false
+            }
+            self.reserve_price.remove(&auction_id);
+            self.reserve_commitment.insert(auction_id, commitment);
+            true
+        }
+
+        /// lets the beneficiary open the lot's hidden reserve price, usually right before ending the lot
+        #[ink(message)]
+        fn reveal_reserve_price(&mut self, auction_id: AuctionId, reserve_price: Balance, salt: Hash) -> bool {
+            if self.get_beneficiary(auction_id) != self.env().caller() {
+                self.env().emit_event(Not_Beneficiary {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    beneficiary: Some(self.get_beneficiary(auction_id)),
+                });
+                return false
+            }
+            else if self.reserve_commitment.get(&auction_id) != Some(&self.compute_commitment(reserve_price, salt)) {
+                return false
+            }
+
+            self.reserve_commitment.remove(&auction_id);
+            self.reserve_price.insert(auction_id, reserve_price);
+            self.env().emit_event(Reserve_Revealed {
+                auction_id,
+                reserve_price,
+            });
+            true
+        }
+
+        /// lets the beneficiary of an english lot offer it for `price`, which has to be above the starting price,
+        /// before the first bid. A bid of at least that much wins the lot instantly. The offer is withdrawn once the
+        /// highest bid passes `threshold` basis points of it
+        #[ink(message)]
+        fn set_buy_now_price(&mut self, auction_id: AuctionId, price: Balance, threshold: u32) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+            else if self.get_kind(auction_id) != AuctionKind::English {
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    kind: self.get_kind(auction_id),
+                });
+                return false
+            }
+            // otherwise any bid, even one the starting price refuses, would win the lot
+            else if price <= self.get_starting_price(auction_id) {
+                self.env().emit_event(Invalid_Buy_Now_Price {
+                    auction_id,
+                    price,
+                    starting_price: self.get_starting_price(auction_id),
+                });
+                return false
+            }
+
+            self.buy_now_price.insert(auction_id, price);
+            self.buy_now_threshold.insert(auction_id, core::cmp::min(threshold, BASIS_POINTS));
+            true
+        }
+
+        /// lets the beneficiary restrict who may bid on the lot, or open it to everyone again, before the first bid.
+        /// Lots are created `Eligibility::Open`
+        #[ink(message)]
+        fn set_eligibility(&mut self, auction_id: AuctionId, eligibility: Eligibility) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+            self.eligibility.insert(auction_id, eligibility);
+            true
+        }
+
+        /// lets the beneficiary add accounts to the lot's allowlist, which only matters while the lot's eligibility
+        /// is `Eligibility::Allowlist`
+        #[ink(message)]
+        fn add_to_allowlist(&mut self, auction_id: AuctionId, accounts: Vec<AccountId>) -> bool {
+            if !self.can_manage_allowlist(auction_id) {
+                return false
+            }
+            for account in accounts {
+                self.allowlist.insert((auction_id, account), true);
+            }
+            true
+        }
+
+        /// lets the beneficiary take accounts off the lot's allowlist, bids they already made stay valid
+        #[ink(message)]
+        fn remove_from_allowlist(&mut self, auction_id: AuctionId, accounts: Vec<AccountId>) -> bool {
+            if !self.can_manage_allowlist(auction_id) {
+                return false
+            }
+            for account in accounts {
+                self.allowlist.remove(&(auction_id, account));
+            }
+            true
+        }
+
+        /// returns whether the caller may change the lot's allowlist, i.e. is its beneficiary and the lot is not ended
+        fn can_manage_allowlist(&self, auction_id: AuctionId) -> bool {
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                });
+                return false
+            }
+            else if self.get_beneficiary(auction_id) != self.env().caller() {
+                self.env().emit_event(Not_Beneficiary {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    beneficiary: Some(self.get_beneficiary(auction_id)),
+                });
+                return false
+            }
+            !self.is_ended(auction_id)
+        }
+
+        /// lets the caller prove they may bid on a lot restricted to a Merkle root. `proof` holds the sibling hashes
+        /// from the caller's leaf, the blake2 hash of their encoded account id, up to the root, pairs being hashed
+        /// in ascending order. Returns whether the proof was accepted
+        #[ink(message)]
+        fn prove_eligibility(&mut self, auction_id: AuctionId, proof: Vec<Hash>) -> bool {
+            let caller = self.env().caller();
+            let root = match self.get_eligibility(auction_id) {
+                Eligibility::MerkleRoot(root) => root,
+                _ => return false,
+            };
+            let leaf = Hash::from(scale::Encode::using_encoded(&caller, ink_core::hash::blake2_256));
+            let computed = proof.into_iter().fold(leaf, |node, sibling| self.hash_pair(node, sibling));
+            if computed != root {
+                self.env().emit_event(Not_Eligible {
+                    auction_id,
+                    bidder: Some(caller),
+                    eligibility: Eligibility::MerkleRoot(root),
+                });
+                return false
+            }
+            self.proven.insert((auction_id, caller), root);
+            true
+        }
+
+        /// returns the parent of two Merkle tree nodes, the smaller one being hashed first
+        fn hash_pair(&self, a: Hash, b: Hash) -> Hash {
+            let pair = if a.as_ref() <= b.as_ref() { (a, b) } else { (b, a) };
+            Hash::from(scale::Encode::using_encoded(&pair, ink_core::hash::blake2_256))
+        }
+
+        /// lets the beneficiary of an english lot require every bidder to register a bond of `bond` before bidding,
+        /// before the first bid. Bonds are returned with `release_bond` once the lot is ended, except for the
+        /// winner's: the winner has 'confirm_window' milliseconds (or blocks) after `end` to `confirm_purchase` or
+        /// their bond is forfeited to the beneficiary and the lot is offered to the runner-up at the runner-up's bid
+        #[ink(message)]
+        fn set_bid_bond(&mut self, auction_id: AuctionId, bond: Balance, confirm_window: u64) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+            else if self.get_kind(auction_id) != AuctionKind::English {
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    kind: self.get_kind(auction_id),
+                });
+                return false
+            }
+
+            self.bid_bond.insert(auction_id, bond);
+            self.confirm_window.insert(auction_id, confirm_window);
+            true
+        }
+
+        /// To call this funciton, money must be sent to the contract. Adds it to the sender's bond on the lot,
+        /// which has to reach the lot's bid bond before the sender can bid
+        #[ink(message)]
+        fn register_bond(&mut self, auction_id: AuctionId) -> Result<(), AuctionError> {
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
+            let amount: Balance = self.env().transferred_balance();
+            let sender = self.env().caller();
+            let result = self.place_bond(auction_id, sender, amount);
+            self.revert_if_rejected(sender, amount, result)
+        }
+
+        /// Works like `register_bond` in a house with a payment token, `amount` tokens are pulled from the sender
+        /// who must have approved the auction contract to transfer them
+        #[ink(message)]
+        fn register_bond_tokens(&mut self, auction_id: AuctionId, amount: Balance) -> Result<(), AuctionError> {
+            let sender = self.env().caller();
+            self.receive_tokens(sender, amount)?;
+            let result = self.place_bond(auction_id, sender, amount);
+            self.revert_if_rejected(sender, amount, result)
+        }
+
+        /// adds `amount`, already held by the contract, to `sender`'s bond on the lot
+        fn place_bond(&mut self, auction_id: AuctionId, sender: AccountId, amount: Balance) -> Result<(), AuctionError> {
+            if !self.exists(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(sender),
+                });
+                return Err(AuctionError::NoSuchAuction)
+            }
+            else if self.get_bid_bond(auction_id) == 0 {
+                self.add_pending_returns(sender, amount);
+                return Err(AuctionError::NoBondRequired)
+            }
+            else if self.is_ended(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.env().emit_event(Already_Ended {
+                    auction_id,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return Err(AuctionError::BiddingClosed)
+            }
+
+            let bond = self.get_bond(auction_id, sender) + amount;
+            self.bonds.insert((auction_id, sender), bond);
+            self.env().emit_event(Bond_Registered {
+                auction_id,
+                bidder: Some(sender),
+                bond,
+            });
+            Ok(())
+        }
+
+        /// returns whether the account may bid on the lot as far as bonds are concerned
+        fn has_bond(&self, auction_id: AuctionId, id: AccountId) -> bool {
+            self.get_bond(auction_id, id) >= self.get_bid_bond(auction_id)
+        }
+
+        /// Once a lot is ended anyone can return a bidder's bond to the bidder's withdraw balance, unless the bidder
+        /// is the winner whose purchase still awaits confirmation or the runner-up who may still be offered the lot
+        #[ink(message)]
+        fn release_bond(&mut self, auction_id: AuctionId, bidder: AccountId) -> bool {
+            let bond = self.get_bond(auction_id, bidder);
+            // the runner-up may still be offered the lot, so their bond stays locked as well
+            let at_stake = bidder == self.get_highest_bidder(auction_id)
+                || self.runner_up.get(&auction_id) == Some(&bidder);
+            if !self.is_ended(auction_id) || bond == 0 || (self.is_awaiting_confirmation(auction_id) && at_stake) {
+                return false
+            }
+
+            self.bonds.remove(&(auction_id, bidder));
+            self.add_pending_returns(bidder, bond);
+            self.env().emit_event(Bond_Released {
+                auction_id,
+                bidder: Some(bidder),
+                bond,
+            });
+            true
+        }
+
+        /// gives the highest bidder of an ended bonded lot until the end of the confirm window to confirm the purchase
+        fn await_confirmation(&mut self, auction_id: AuctionId) {
+            let deadline = self.get_time().saturating_add(self.get_confirm_window(auction_id));
+            self.confirm_deadline.insert(auction_id, deadline);
+            self.env().emit_event(Awaiting_Confirmation {
+                auction_id,
+                winner: Some(self.get_highest_bidder(auction_id)),
+                price: self.get_highest_bid(auction_id),
+                deadline,
+            });
+        }
+
+        /// Lets the winner of a bonded lot confirm the purchase before the deadline, after which the lot is paid out
+        /// like `end` does and the winner's bond is added to their withdraw balance. A runner-up the lot was offered
+        /// to pays their bid out of their withdraw balance, to which the money sent with the call is added first
+        #[ink(message)]
+        fn confirm_purchase(&mut self, auction_id: AuctionId) -> Result<(), AuctionError> {
+            let caller = self.env().caller();
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
+            let transferred: Balance = self.env().transferred_balance();
+            self.add_pending_returns(caller, transferred);
+            let result = self.complete_purchase(auction_id, caller);
+            self.revert_if_rejected(caller, transferred, result)
+        }
+
+        /// completes the purchase of the bonded lot by `caller`, see `confirm_purchase`
+        fn complete_purchase(&mut self, auction_id: AuctionId, caller: AccountId) -> Result<(), AuctionError> {
+            if !self.is_awaiting_confirmation(auction_id) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(caller),
+                    time: self.get_time(),
+                });
+                return Err(AuctionError::NotAwaitingConfirmation)
+            }
+            else if self.get_highest_bidder(auction_id) != caller {
+                return Err(AuctionError::NotHighestBidder)
+            }
+            else if self.get_time() > self.get_confirm_deadline(auction_id).unwrap_or(0) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(caller),
+                    time: self.get_time(),
+                });
+                return Err(AuctionError::WrongPhase)
+            }
+
+            let price = self.get_highest_bid(auction_id);
+            if *self.offer_unpaid.get(&auction_id).unwrap_or(&false) {
+                let available = self.curr_withdrawl_amount(caller);
+                if available < price {
+                    self.env().emit_event(Insufficient_Pending_Returns {
+                        auction_id,
+                        bidder: Some(caller),
+                        requested: price,
+                        available,
+                    });
+                    return Err(AuctionError::InsufficientPendingReturns)
+                }
+                self.pending_returns.insert(caller, available - price);
+                self.offer_unpaid.insert(auction_id, false);
+            }
+
+            self.confirm_deadline.remove(&auction_id);
+            self.release_bond(auction_id, caller);
+            self.env().emit_event(Purchase_Confirmed {
+                auction_id,
+                winner: Some(caller),
+                price,
+            });
+            self.pay_out(auction_id);
+            self.deliver_asset_of(auction_id);
+            Ok(())
+        }
+
+        /// Once the deadline of a purchase awaiting confirmation has passed anyone can forfeit the winner's bond to
+        /// the beneficiary. The winner's bid is added to their withdraw balance and the lot is offered to the
+        /// runner-up at their bid, with a new deadline, or goes unsold if there is no runner-up
+        #[ink(message)]
+        fn forfeit_bond(&mut self, auction_id: AuctionId) -> bool {
+            if !self.is_awaiting_confirmation(auction_id)
+                || self.get_time() <= self.get_confirm_deadline(auction_id).unwrap_or(0) {
+                return false
+            }
+
+            let winner = self.get_highest_bidder(auction_id);
+            let bond = self.get_bond(auction_id, winner);
+            self.bonds.remove(&(auction_id, winner));
+            self.add_pending_returns(self.get_beneficiary(auction_id), bond);
+            if !*self.offer_unpaid.get(&auction_id).unwrap_or(&false) {
+                self.add_pending_returns(winner, self.get_locked_amount(auction_id));
+            }
+            self.proxy_max.insert(auction_id, 0);
+            self.env().emit_event(Bond_Forfeited {
+                auction_id,
+                bidder: Some(winner),
+                bond,
+            });
+
+            // the runner-up gets one chance to buy the lot at their own bid
+            match self.runner_up.get(&auction_id).cloned() {
+                Some(runner_up) if runner_up != winner && self.get_second_highest_bid(auction_id) > 0 => {
+                    self.highest_bidder.insert(auction_id, runner_up);
+                    self.highest_bid.insert(auction_id, self.get_second_highest_bid(auction_id));
+                    self.second_highest_bid.insert(auction_id, 0);
+                    self.runner_up.remove(&auction_id);
+                    self.offer_unpaid.insert(auction_id, true);
+                    self.await_confirmation(auction_id);
+                }
+                _ => {
+                    self.confirm_deadline.remove(&auction_id);
+                    self.offer_unpaid.insert(auction_id, false);
+                    self.highest_bidder.insert(auction_id, self.get_beneficiary(auction_id));
+                    self.highest_bid.insert(auction_id, 0);
+                    self.pay_out(auction_id);
+                    self.deliver_asset_of(auction_id);
+                }
+            }
+            true
+        }
+
+        /// returns the bond an account has to register before bidding on the lot, 0 if none
+        #[ink(message)]
+        fn get_bid_bond(&self, auction_id: AuctionId) -> Balance {
+            *self.bid_bond.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns how long the winner of the bonded lot has to confirm the purchase in milliseconds (or blocks)
+        #[ink(message)]
+        fn get_confirm_window(&self, auction_id: AuctionId) -> Timestamp {
+            *self.confirm_window.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the bond the account registered on the lot
+        #[ink(message)]
+        fn get_bond(&self, auction_id: AuctionId, id: AccountId) -> Balance {
+            *self.bonds.get(&(auction_id, id)).unwrap_or(&0)
+        }
+
+        /// returns whether the lot's highest bidder still has to confirm the purchase
+        #[ink(message)]
+        fn is_awaiting_confirmation(&self, auction_id: AuctionId) -> bool {
+            self.confirm_deadline.get(&auction_id).is_some()
+        }
+
+        /// returns the deadline of the purchase awaiting confirmation, if any
+        #[ink(message)]
+        fn get_confirm_deadline(&self, auction_id: AuctionId) -> Option<Timestamp> {
+            self.confirm_deadline.get(&auction_id).cloned()
+        }
+
+        /// lets the beneficiary settle the lot in escrow, before the first bid. Once the lot is ended the price is
+        /// held by the contract until the winner confirms delivery with `confirm_delivery` or `timeout` milliseconds
+        /// (or blocks) have passed. Until then the winner or the beneficiary can raise a dispute, which `arbitrator`
+        /// settles by splitting the price between them. If the arbitrator hasn't acted `dispute_timeout` milliseconds
+        /// (or blocks) after the dispute was raised anyone can resolve it with `default_winner_share` basis points
+        /// going to the winner. The arbitrator can't be the beneficiary and multi-unit lots cannot be escrowed
+        #[ink(message)]
+        fn set_escrow(&mut self, auction_id: AuctionId, arbitrator: AccountId, timeout: u64, dispute_timeout: u64,
+                      default_winner_share: u32) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+            else if arbitrator == self.get_beneficiary(auction_id) {
+                self.env().emit_event(Invalid_Arbitrator {
+                    auction_id,
+                    arbitrator: Some(arbitrator),
+                });
+                return false
+            }
+            else if self.get_kind(auction_id) == AuctionKind::MultiUnit {
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    kind: self.get_kind(auction_id),
+                });
+                return false
+            }
+
+            self.escrow_arbitrator.insert(auction_id, arbitrator);
+            self.escrow_timeout.insert(auction_id, timeout);
+            self.dispute_timeout.insert(auction_id, dispute_timeout);
+            self.default_winner_share.insert(auction_id, core::cmp::min(default_winner_share, BASIS_POINTS));
+            true
+        }
+
+        /// keeps the price of an escrowed lot in the contract until delivery is confirmed
+        fn hold_in_escrow(&mut self, auction_id: AuctionId, amount: Balance) {
+            let deadline = self.get_time().saturating_add(self.get_escrow_timeout(auction_id));
+            self.escrowed_amount.insert(auction_id, amount);
+            self.escrow_deadline.insert(auction_id, deadline);
+            self.escrow_state.insert(auction_id, EscrowState::AwaitingDelivery);
+            self.env().emit_event(Escrow_Held {
+                auction_id,
+                winner: Some(self.get_highest_bidder(auction_id)),
+                amount,
+                deadline,
+            });
+        }
+
+        /// returns whether the escrow of the lot is in `state`, emitting an event if not
+        fn in_escrow_state(&self, auction_id: AuctionId, state: EscrowState) -> bool {
+            if self.get_escrow_state(auction_id) != Some(state) {
+                self.env().emit_event(Wrong_Escrow_State {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    state: self.get_escrow_state(auction_id),
+                });
+                return false
+            }
+            true
+        }
+
+        /// pays the escrowed price out to the beneficiary (and the platform fee and royalties)
+        fn release_escrowed(&mut self, auction_id: AuctionId) {
+            let amount = self.get_escrowed_amount(auction_id);
+            self.escrow_state.insert(auction_id, EscrowState::Released);
+            self.distribute_proceeds(auction_id, amount);
+            self.env().emit_event(Escrow_Released {
+                auction_id,
+                sender: Some(self.env().caller()),
+                amount,
+            });
+        }
+
+        /// lets the winner of an escrowed lot confirm delivery, which releases the price to the beneficiary
+        #[ink(message)]
+        fn confirm_delivery(&mut self, auction_id: AuctionId) -> bool {
+            if !self.in_escrow_state(auction_id, EscrowState::AwaitingDelivery) {
+                return false
+            }
+            else if self.get_highest_bidder(auction_id) != self.env().caller() {
+                return false
+            }
+
+            self.release_escrowed(auction_id);
+            true
+        }
+
+        /// Once the timeout of an escrowed lot has passed without a dispute anyone can release the price to the
+        /// beneficiary
+        #[ink(message)]
+        fn release_escrow(&mut self, auction_id: AuctionId) -> bool {
+            if !self.in_escrow_state(auction_id, EscrowState::AwaitingDelivery) {
+                return false
+            }
+            else if self.get_time() <= self.get_escrow_deadline(auction_id).unwrap_or(0) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    time: self.get_time(),
+                });
+                return false
+            }
+
+            self.release_escrowed(auction_id);
+            true
+        }
+
+        /// lets the winner or the beneficiary of an escrowed lot dispute it until the timeout. From then on only the
+        /// arbitrator can pay out the price, until the dispute timeout passes and `resolve_by_default` can be called
+        #[ink(message)]
+        fn raise_dispute(&mut self, auction_id: AuctionId) -> bool {
+            let caller = self.env().caller();
+            if !self.in_escrow_state(auction_id, EscrowState::AwaitingDelivery) {
+                return false
+            }
+            else if caller != self.get_highest_bidder(auction_id) && caller != self.get_beneficiary(auction_id) {
+                return false
+            }
+            else if self.get_time() > self.get_escrow_deadline(auction_id).unwrap_or(0) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(caller),
+                    time: self.get_time(),
+                });
+                return false
+            }
+
+            self.escrow_state.insert(auction_id, EscrowState::Disputed);
+            self.escrow_deadline.insert(auction_id, self.get_time().saturating_add(self.get_dispute_timeout(auction_id)));
+            self.env().emit_event(Dispute_Raised {
+                auction_id,
+                raised_by: Some(caller),
+            });
+            true
+        }
+
+        /// lets the arbitrator of a disputed lot return `winner_basis_points` of the escrowed price to the winner's
+        /// withdraw balance and pay the rest out to the beneficiary (and the platform fee and royalties)
+        #[ink(message)]
+        fn resolve_dispute(&mut self, auction_id: AuctionId, winner_basis_points: u32) -> bool {
+            if !self.in_escrow_state(auction_id, EscrowState::Disputed) {
+                return false
+            }
+            else if self.get_escrow_arbitrator(auction_id) != Some(self.env().caller()) {
+                return false
+            }
+
+            self.split_escrowed(auction_id, winner_basis_points);
+            true
+        }
+
+        /// Once the dispute timeout of a disputed lot has passed without a ruling anyone can resolve it, the winner
+        /// getting the lot's default share of the escrowed price and the beneficiary the rest
+        #[ink(message)]
+        fn resolve_by_default(&mut self, auction_id: AuctionId) -> bool {
+            if !self.in_escrow_state(auction_id, EscrowState::Disputed) {
+                return false
+            }
+            else if self.get_time() <= self.get_escrow_deadline(auction_id).unwrap_or(0) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    time: self.get_time(),
+                });
+                return false
+            }
+
+            self.split_escrowed(auction_id, self.get_default_winner_share(auction_id));
+            true
+        }
+
+        /// returns `winner_basis_points` of the escrowed price to the winner's withdraw balance and pays the rest
+        /// out to the beneficiary (and the platform fee and royalties)
+        fn split_escrowed(&mut self, auction_id: AuctionId, winner_basis_points: u32) {
+            let amount = self.get_escrowed_amount(auction_id);
+            let winner_amount = self.basis_points_of(amount, core::cmp::min(winner_basis_points, BASIS_POINTS));
+            let beneficiary_amount = amount - winner_amount;
+            self.escrow_state.insert(auction_id, EscrowState::Resolved);
+            self.add_pending_returns(self.get_highest_bidder(auction_id), winner_amount);
+            self.distribute_proceeds(auction_id, beneficiary_amount);
+            self.env().emit_event(Dispute_Resolved {
+                auction_id,
+                winner_amount,
+                beneficiary_amount,
+            });
+        }
+
+        /// returns the arbitrator of an escrowed lot, None if the lot is not escrowed
+        #[ink(message)]
+        fn get_escrow_arbitrator(&self, auction_id: AuctionId) -> Option<AccountId> {
+            self.escrow_arbitrator.get(&auction_id).cloned()
+        }
+
+        /// returns how long the winner of the escrowed lot has to confirm delivery in milliseconds (or blocks)
+        #[ink(message)]
+        fn get_escrow_timeout(&self, auction_id: AuctionId) -> Timestamp {
+            *self.escrow_timeout.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns how long the arbitrator of the escrowed lot has to settle a dispute in milliseconds (or blocks)
+        #[ink(message)]
+        fn get_dispute_timeout(&self, auction_id: AuctionId) -> Timestamp {
+            *self.dispute_timeout.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the basis points of the escrowed price the winner gets if the arbitrator doesn't settle a dispute
+        #[ink(message)]
+        fn get_default_winner_share(&self, auction_id: AuctionId) -> u32 {
+            *self.default_winner_share.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns where the winning bid of the escrowed lot stands, None until the price is held
+        #[ink(message)]
+        fn get_escrow_state(&self, auction_id: AuctionId) -> Option<EscrowState> {
+            self.escrow_state.get(&auction_id).cloned()
+        }
+
+        /// returns the time after which the escrowed price can be released without confirmation, or once disputed
+        /// the time after which the dispute can be resolved by default, if held
+        #[ink(message)]
+        fn get_escrow_deadline(&self, auction_id: AuctionId) -> Option<Timestamp> {
+            self.escrow_deadline.get(&auction_id).cloned()
+        }
+
+        /// returns the price held in escrow for the lot
+        #[ink(message)]
+        fn get_escrowed_amount(&self, auction_id: AuctionId) -> Balance {
+            *self.escrowed_amount.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// lets the beneficiary offer the lot to `proposed`, who becomes the beneficiary once they call
+        /// `accept_beneficiary`. A new proposal replaces the previous one. Only possible before the lot is ended
+        #[ink(message)]
+        fn propose_beneficiary(&mut self, auction_id: AuctionId, proposed: AccountId) -> bool {
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                });
+                return false
+            }
+            else if self.get_beneficiary(auction_id) != self.env().caller() {
+                self.env().emit_event(Not_Beneficiary {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    beneficiary: Some(self.get_beneficiary(auction_id)),
+                });
+                return false
+            }
+            else if self.is_ended(auction_id) {
+                self.env().emit_event(Already_Ended {
+                    auction_id,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return false
+            }
+
+            self.proposed_beneficiary.insert(auction_id, proposed);
+            self.env().emit_event(Beneficiary_Proposed {
+                auction_id,
+                beneficiary: Some(self.get_beneficiary(auction_id)),
+                proposed: Some(proposed),
+            });
+            true
+        }
+
+        /// lets the account the lot was proposed to take it over as its beneficiary, before the lot is ended
+        #[ink(message)]
+        fn accept_beneficiary(&mut self, auction_id: AuctionId) -> bool {
+            let caller = self.env().caller();
+            if self.get_proposed_beneficiary(auction_id) != Some(caller) {
+                return false
+            }
+            // the arbitrator of an escrowed lot can't rule on their own sale
+            else if self.get_escrow_arbitrator(auction_id) == Some(caller) {
+                self.env().emit_event(Invalid_Arbitrator {
+                    auction_id,
+                    arbitrator: Some(caller),
+                });
+                return false
+            }
+            else if self.is_ended(auction_id) {
+                self.env().emit_event(Already_Ended {
+                    auction_id,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return false
+            }
+
+            let previous = self.get_beneficiary(auction_id);
+            self.proposed_beneficiary.remove(&auction_id);
+            self.beneficiary.insert(auction_id, caller);
+            self.env().emit_event(Beneficiary_Transferred {
+                auction_id,
+                previous: Some(previous),
+                beneficiary: Some(caller),
+            });
+            true
+        }
+
+        /// returns the account the lot was offered to by its beneficiary, if any
+        #[ink(message)]
+        fn get_proposed_beneficiary(&self, auction_id: AuctionId) -> Option<AccountId> {
+            self.proposed_beneficiary.get(&auction_id).cloned()
+        }
+
+        /// lets the beneficiary describe what is being sold, until the first bid. `content_hash` points to the
+        /// full description, e.g. an IPFS CID
+        #[ink(message)]
+        fn set_metadata(&mut self, auction_id: AuctionId, title: String, content_hash: String, category: String) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+
+            self.metadata.insert(auction_id, Metadata { title, content_hash, category });
+            self.env().emit_event(Metadata_Set { auction_id });
+            true
+        }
+
+        /// returns the lot's metadata, empty if none was set
+        #[ink(message)]
+        fn get_metadata(&self, auction_id: AuctionId) -> Metadata {
+            self.metadata.get(&auction_id).cloned().unwrap_or_default()
+        }
+
+        /// returns the lot's metadata along with its beneficiary and bidding state, None if the lot doesn't exist
+        #[ink(message)]
+        fn get_info(&self, auction_id: AuctionId) -> Option<LotInfo> {
+            if !self.exists(auction_id) {
+                return None
+            }
+
+            Some(LotInfo {
+                kind: self.get_kind(auction_id),
+                beneficiary: self.get_beneficiary(auction_id),
+                proposed_beneficiary: self.get_proposed_beneficiary(auction_id),
+                metadata: self.get_metadata(auction_id),
+                starting_price: self.get_starting_price(auction_id),
+                highest_bid: self.get_highest_bid(auction_id),
+                highest_bidder: self.get_highest_bidder(auction_id),
+                start_time: self.get_start_time(auction_id),
+                end_time: self.get_end_time(auction_id),
+                is_ended: self.is_ended(auction_id),
+            })
+        }
+
+        /// lets the beneficiary choose before the first bid when they may cancel the lot, lots are created with
+        /// `CancelPolicy::BeforeBids`
+        #[ink(message)]
+        fn set_cancel_policy(&mut self, auction_id: AuctionId, policy: CancelPolicy) -> bool {
+            if !self.can_configure(auction_id) {
+                return false
+            }
+            let policy = match policy {
+                CancelPolicy::WithPenalty(bps) => CancelPolicy::WithPenalty(core::cmp::min(bps, BASIS_POINTS)),
+                policy => policy,
+            };
+            self.cancel_policy.insert(auction_id, policy);
+            true
+        }
+
+        /// lets the beneficiary call the lot off when its cancel policy allows it. The lot ends without a winner,
+        /// the highest bidder's whole bid (including any proxy escrow) is added to their withdraw balance and an
+        /// escrowed asset goes back to the beneficiary. Under `CancelPolicy::WithPenalty` the penalty is taken from
+        /// the beneficiary's withdraw balance, to which the money sent with the call is added first, and added to
+        /// the highest bidder's. Unrevealed deposits of a cancelled sealed lot can be released in full
+        #[ink(message)]
+        fn cancel(&mut self, auction_id: AuctionId) -> bool {
+            let caller = self.env().caller();
+            if !self.accepts_native() {
+                return false
+            }
+            //money sent along is kept for the caller whatever happens
+            self.add_pending_returns(caller, self.env().transferred_balance());
+
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(caller),
+                });
+                return false
+            }
+            else if self.get_beneficiary(auction_id) != caller {
+                self.env().emit_event(Not_Beneficiary {
+                    auction_id,
+                    sender: Some(caller),
+                    beneficiary: Some(self.get_beneficiary(auction_id)),
+                });
+                return false
+            }
+            else if self.is_ended(auction_id) {
+                self.env().emit_event(Already_Ended {
+                    auction_id,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return false
+            }
+
+            let highest_bid = self.get_highest_bid(auction_id);
+            let policy = self.get_cancel_policy(auction_id);
+            let penalty = match policy {
+                CancelPolicy::BeforeBids => 0,
+                CancelPolicy::WithPenalty(bps) => self.basis_points_of(highest_bid, bps),
+            };
+            if (policy == CancelPolicy::BeforeBids && highest_bid > 0)
+                || self.curr_withdrawl_amount(caller) < penalty {
+                self.env().emit_event(Cancel_Not_Allowed {
+                    auction_id,
+                    policy,
+                    highest_bid,
+                });
+                return false
+            }
+
+            self.ended.insert(auction_id, true);
+            self.cancelled.insert(auction_id, true);
+
+            let highest_bidder = self.get_highest_bidder(auction_id);
+            // the bids on multi-unit and candle lots are all returned by `settle_units` and `release_candle_bid`
+            let refunded = if highest_bid > 0
+                    && self.get_kind(auction_id) != AuctionKind::MultiUnit
+                    && self.get_kind(auction_id) != AuctionKind::Candle {
+                self.get_locked_amount(auction_id)
+            } else {
+                0
+            };
+            self.pending_returns.insert(caller, self.curr_withdrawl_amount(caller) - penalty);
+            self.add_pending_returns(highest_bidder, refunded + penalty);
+            self.deliver_asset_of(auction_id);
+
+            self.env().emit_event(Cancelled {
+                auction_id,
+                highest_bidder: Some(highest_bidder),
+                refunded,
+                penalty,
+            });
+            true
+        }
+
+        /// returns whether a reserve price can be set on the lot, dutch lots already have a floor price and the
+        /// starting price of multi-unit lots is a reserve for every unit
+        fn takes_reserve(&self, auction_id: AuctionId) -> bool {
+            if self.get_kind(auction_id) == AuctionKind::Dutch || self.get_kind(auction_id) == AuctionKind::MultiUnit {
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    kind: self.get_kind(auction_id),
+                });
+                return false
+            }
+            true
+        }
+
+        /// returns whether the caller may still change the lot's settings, i.e. is its beneficiary
+        /// and the lot is neither ended nor has it been bid on
+        fn can_configure(&self, auction_id: AuctionId) -> bool {
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                });
+                return false
+            }
+            else if self.get_beneficiary(auction_id) != self.env().caller() {
+                self.env().emit_event(Not_Beneficiary {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    beneficiary: Some(self.get_beneficiary(auction_id)),
+                });
+                return false
+            }
+            // a commitment on a sealed lot counts as a bid, even though the highest bid only moves on reveal
+            else if self.is_ended(auction_id) || self.get_highest_bid(auction_id) > 0
+                || self.get_commitment_count(auction_id) > 0 {
+                self.env().emit_event(Config_Locked {
+                    auction_id,
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return false
+            }
+            true
+        }
+
+        /// creates a new lot like `create_auction` that escrows `asset`, which the caller must have approved the
+        /// auction contract to transfer. The asset goes to the winner when the lot is settled, or back to the
+        /// beneficiary if there was no valid bid. Returns None and creates no lot if the asset can't be escrowed
+        #[ink(message)]
+        fn create_asset_auction(&mut self, init_value: Balance, millisecs: u64, asset: Asset) -> Option<AuctionId> {
+            let caller = self.env().caller();
+            if !self.transfer_asset(asset, caller, self.env().account_id()) {
+                self.env().emit_event(Asset_Transfer_Failed {
+                    auction_id: *self.next_auction_id,
+                    recipient: Some(self.env().account_id()),
+                    asset,
+                });
+                return None
+            }
+
+            let auction_id = self.create_lot(AuctionKind::English, caller, init_value, self.get_time(), millisecs);
+            self.asset.insert(auction_id, asset);
+            self.asset_delivered.insert(auction_id, false);
+
+            self.env().emit_event(Asset_Escrowed {
+                auction_id,
+                beneficiary: Some(caller),
+                asset,
+            });
+            Some(auction_id)
+        }
+
+        /// moves `asset` from `from` to `to` with a cross-contract call and returns whether it succeeded,
+        /// assets held by the auction contract are moved with `transfer`, others with `transfer_from`
+        fn transfer_asset(&self, asset: Asset, from: AccountId, to: AccountId) -> bool {
+            let contract = match asset {
+                Asset::Nft { contract, .. } | Asset::Fungible { contract, .. } => contract,
+            };
+            let call = if from == self.env().account_id() {
+                CallParams::<EnvTypes, TransferResult>::eval(contract, Selector::from_str("transfer"))
+            } else {
+                CallParams::<EnvTypes, TransferResult>::eval(contract, Selector::from_str("transfer_from"))
+                    .push_arg(&from)
+            }.push_arg(&to);
+            let call = match asset {
+                Asset::Nft { token_id, .. } => call.push_arg(&token_id),
+                Asset::Fungible { amount, .. } => call.push_arg(&amount),
+            };
+            // anything but a decoded `Ok(())` is a failure, including a return value that does not decode
+            cross_contract(|| call.fire()) == Some(Ok(()))
+        }
+
+        /// hands the lot's escrowed asset to the winner, or to the beneficiary if there was no valid bid.
+        /// If the transfer fails the asset stays escrowed and `deliver_asset` can be called to retry
+        fn deliver_asset_of(&mut self, auction_id: AuctionId) -> bool {
+            let asset = match self.get_asset(auction_id) {
+                Some(asset) => asset,
+                None => return false,
+            };
+            if self.is_asset_delivered(auction_id) {
+                return false
+            }
+
+            let recipient = self.get_asset_recipient(auction_id);
+            // mark the asset as delivered before calling out so the call can't deliver it twice
+            self.asset_delivered.insert(auction_id, true);
+            if !self.transfer_asset(asset, self.env().account_id(), recipient) {
+                self.asset_delivered.insert(auction_id, false);
+                self.env().emit_event(Asset_Transfer_Failed {
+                    auction_id,
+                    recipient: Some(recipient),
+                    asset,
+                });
+                return false
+            }
+
+            self.env().emit_event(Asset_Delivered {
+                auction_id,
+                recipient: Some(recipient),
+                asset,
+            });
+            true
+        }
+
+        /// retries handing over the escrowed asset of an ended lot whose delivery failed, anyone can call it
+        #[ink(message)]
+        fn deliver_asset(&mut self, auction_id: AuctionId) -> bool {
+            let awaiting_draw = self.get_candle_end_block(auction_id).is_some()
+                && self.get_candle_close(auction_id).is_none();
+            if !self.is_ended(auction_id) || self.is_awaiting_confirmation(auction_id) || awaiting_draw {
+                return false
+            }
+            self.deliver_asset_of(auction_id)
+        }
+
+        /// stores a new lot of the given kind for `beneficiary` and returns its id, bidding opens at `start_time`
+        /// (or right away if that has passed) and lasts 'millisecs' milliseconds (or blocks)
+        fn create_lot(&mut self, kind: AuctionKind, beneficiary: AccountId, init_value: Balance, start_time: Timestamp,
+                      millisecs: u64) -> AuctionId {
+            let auction_id = *self.next_auction_id;
+            self.next_auction_id.set(auction_id + 1);
+
+            self.kind.insert(auction_id, kind);
+            self.beneficiary.insert(auction_id, beneficiary);
+            self.highest_bidder.insert(auction_id, beneficiary);
+            self.starting_price.insert(auction_id, init_value);
+            self.highest_bid.insert(auction_id, 0);
+            self.second_highest_bid.insert(auction_id, 0);
+            self.price_rule.insert(auction_id, PriceRule::FirstPrice);
+            self.ended.insert(auction_id, false);
+            self.total_extension.insert(auction_id, 0);
+
+            // Timestamps are in milliseconds, or block numbers in a house measuring time in blocks
+            let curr_time: Timestamp = self.get_time();
+            let start_time = core::cmp::max(start_time, curr_time);
+            self.created_time.insert(auction_id, curr_time);
+            if start_time > curr_time {
+                self.start_time.insert(auction_id, start_time);
+            }
+            self.end_time.insert(auction_id, start_time.saturating_add(millisecs));
+
+            // emit event
+            self.env().emit_event(Created {
+                auction_id,
+                beneficiary: Some(beneficiary),
+                starting_bid: init_value,
+                created_time: curr_time,
+                start_time,
+                end_time: start_time.saturating_add(millisecs),
+                time_unit: self.get_time_unit(),
+            });
+            auction_id
+        }
+
+        /// returns whether a lot with the given id has been created
+        fn exists(&self, auction_id: AuctionId) -> bool {
+            auction_id < *self.next_auction_id
+        }
+
+        /// adds `amount` to the given account's withdraw balance
+        fn add_pending_returns(&mut self, account: AccountId, amount: Balance) {
+            let curr_pending = self.curr_withdrawl_amount(account);
+            self.pending_returns.insert(account, curr_pending + amount);
+        }
+
+        /// returns `amount * numerator / denominator` rounded down, without overflowing on large amounts
+        fn fraction_of(&self, amount: Balance, numerator: u64, denominator: u64) -> Balance {
+            if denominator == 0 {
+                return 0
+            }
+            let numerator = Balance::from(numerator);
+            let denominator = Balance::from(denominator);
+            amount / denominator * numerator + amount % denominator * numerator / denominator
+        }
+
+        /// returns `bps` basis points of `amount`, rounded down
+        fn basis_points_of(&self, amount: Balance, bps: u32) -> Balance {
+            self.fraction_of(amount, u64::from(bps), u64::from(BASIS_POINTS))
+        }
+
+        /// returns the number of lots created so far, lot ids run from 0 to this value (exclusive)
+        #[ink(message)]
+        fn get_auction_count(&self) -> AuctionId {
+            *self.next_auction_id
+        }
+
+        /// returns the lot's highestBid.
+        fn get_highest_bid(&self, auction_id: AuctionId) -> Balance {
+            *self.highest_bid.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the lot's best valid bid after the highest one.
+        #[ink(message)]
+        fn get_second_highest_bid(&self, auction_id: AuctionId) -> Balance {
+            *self.second_highest_bid.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns what the winner of the lot pays at settlement
+        #[ink(message)]
+        fn get_price_rule(&self, auction_id: AuctionId) -> PriceRule {
+            *self.price_rule.get(&auction_id).unwrap_or(&PriceRule::FirstPrice)
+        }
+
+        /// returns what the lot's current highest bidder would pay if the lot was settled now
+        #[ink(message)]
+        fn get_clearing_price(&self, auction_id: AuctionId) -> Balance {
+            let highest_bid = self.get_highest_bid(auction_id);
+            if highest_bid == 0 {
+                return 0
+            }
+            match self.get_price_rule(auction_id) {
+                PriceRule::FirstPrice => highest_bid,
+                PriceRule::SecondPrice => core::cmp::max(self.get_second_highest_bid(auction_id),
+                                                         self.get_starting_price(auction_id)),
+            }
+        }
+
+        /// returns whether the lot has a reserve price, hidden or not
+        #[ink(message)]
+        fn has_reserve_price(&self, auction_id: AuctionId) -> bool {
+            self.reserve_price.get(&auction_id).is_some() || self.reserve_commitment.get(&auction_id).is_some()
+        }
+
+        /// returns whether the lot's highest bid meets its reserve price without revealing the reserve,
+        /// a hidden reserve is only met once it has been revealed
+        #[ink(message)]
+        fn is_reserve_met(&self, auction_id: AuctionId) -> bool {
+            if self.reserve_commitment.get(&auction_id).is_some() {
+                return false
+            }
+            self.get_highest_bid(auction_id) >= *self.reserve_price.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the price that wins the lot instantly, if it is still on offer
+        #[ink(message)]
+        fn get_buy_now_price(&self, auction_id: AuctionId) -> Option<Balance> {
+            let price = *self.buy_now_price.get(&auction_id)?;
+            let threshold = *self.buy_now_threshold.get(&auction_id).unwrap_or(&0);
+            if self.is_ended(auction_id) || self.get_highest_bid(auction_id) > self.basis_points_of(price, threshold) {
+                return None
+            }
+            Some(price)
+        }
+
+        /// returns the maximum escrowed by the lot's highest bidder through a proxy bid, 0 for a manual bid
+        fn get_proxy_max(&self, auction_id: AuctionId) -> Balance {
+            *self.proxy_max.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns what the lot's highest bidder has locked in the contract, i.e. their proxy maximum or their bid
+        fn get_locked_amount(&self, auction_id: AuctionId) -> Balance {
+            core::cmp::max(self.get_highest_bid(auction_id), self.get_proxy_max(auction_id))
+        }
+
+        /// returns the asset escrowed by the lot, if any
+        #[ink(message)]
+        fn get_asset(&self, auction_id: AuctionId) -> Option<Asset> {
+            self.asset.get(&auction_id).cloned()
+        }
+
+        /// returns whether the lot's escrowed asset has been handed over
+        #[ink(message)]
+        fn is_asset_delivered(&self, auction_id: AuctionId) -> bool {
+            *self.asset_delivered.get(&auction_id).unwrap_or(&false)
+        }
+
+        /// returns who gets the lot's escrowed asset: the highest bidder if the lot sells, the beneficiary otherwise
+        fn get_asset_recipient(&self, auction_id: AuctionId) -> AccountId {
+            if !self.is_cancelled(auction_id) && self.get_highest_bid(auction_id) > 0 && self.is_reserve_met(auction_id) {
+                return self.get_highest_bidder(auction_id)
+            }
+            self.get_beneficiary(auction_id)
+        }
+
+        /// appends a bid to the lot's bid history
+        fn log_bid(&mut self, auction_id: AuctionId, bidder: AccountId, amount: Balance, status: BidStatus) {
+            let index = self.get_bid_count(auction_id);
+            let account_index = self.get_account_bid_count(auction_id, bidder);
+            self.bid_history.insert((auction_id, index), BidRecord {
+                bidder,
+                amount,
+                time: self.get_time(),
+                status,
+            });
+            self.bid_count.insert(auction_id, index + 1);
+            self.account_bids.insert((auction_id, bidder, account_index), index);
+            self.account_bid_count.insert((auction_id, bidder), account_index + 1);
+        }
+
+        /// returns the number of entries in the lot's bid history
+        #[ink(message)]
+        fn get_bid_count(&self, auction_id: AuctionId) -> u32 {
+            *self.bid_count.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns up to `limit` (at most MAX_PAGE_SIZE) entries of the lot's bid history, oldest first, starting at `start`
+        #[ink(message)]
+        fn get_bids(&self, auction_id: AuctionId, start: u32, limit: u32) -> Vec<BidRecord> {
+            let end = core::cmp::min(self.get_bid_count(auction_id),
+                                     start.saturating_add(core::cmp::min(limit, MAX_PAGE_SIZE)));
+            (start..end)
+                .filter_map(|index| self.bid_history.get(&(auction_id, index)).cloned())
+                .collect()
+        }
+
+        /// returns the number of bids the given account made on the lot
+        #[ink(message)]
+        fn get_account_bid_count(&self, auction_id: AuctionId, id: AccountId) -> u32 {
+            *self.account_bid_count.get(&(auction_id, id)).unwrap_or(&0)
+        }
+
+        /// returns up to `limit` (at most MAX_PAGE_SIZE) of the bids the given account made on the lot,
+        /// oldest first, starting at the account's `start`th bid
+        #[ink(message)]
+        fn get_account_bids(&self, auction_id: AuctionId, id: AccountId, start: u32, limit: u32) -> Vec<BidRecord> {
+            let end = core::cmp::min(self.get_account_bid_count(auction_id, id),
+                                     start.saturating_add(core::cmp::min(limit, MAX_PAGE_SIZE)));
+            (start..end)
+                .filter_map(|index| self.account_bids.get(&(auction_id, id, index)))
+                .filter_map(|position| self.bid_history.get(&(auction_id, *position)).cloned())
+                .collect()
+        }
+
+        /// returns the lot's starting_price.
+        #[ink(message)]
+        fn get_starting_price(&self, auction_id: AuctionId) -> Balance {
+            *self.starting_price.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the balance in the contract, in the payment token if the house has one
+        #[ink(message)]
+        fn get_contract_balance(&self) -> Balance {
+            match self.get_payment_token() {
+                Some(token) => {
+                    let call = CallParams::<EnvTypes, Balance>::eval(token, Selector::from_str("balance_of"))
+                        .push_arg(&self.env().account_id());
+                    cross_contract(|| call.fire()).unwrap_or(0)
+                }
+                None => self.env().balance(),
+            }
+        }
+
+        /// returns the token bids and withdrawals are made in, None for native balance
+        #[ink(message)]
+        fn get_payment_token(&self) -> Option<AccountId> {
+            *self.payment_token
+        }
+
+        /// returns whether the money sent with the call can be used, i.e. the house takes native balance or
+        /// nothing was sent. Native balance sent to a token house is sent straight back, the call reverts if that fails
+        fn accepts_native(&mut self) -> bool {
+            let transferred: Balance = self.env().transferred_balance();
+            if self.get_payment_token().is_none() || transferred == 0 {
+                return true
+            }
+
+            let sender = self.env().caller();
+            // pending_returns are kept in the token so the native balance can't be credited there,
+            // reverting keeps it with the sender if it can't be sent back
+            assert!(self.env().transfer(sender, transferred).is_ok(), "cannot return native balance");
+            self.env().emit_event(Wrong_Currency {
+                sender: Some(sender),
+                payment_token: self.get_payment_token(),
+            });
+            false
+        }
+
+        /// pulls `amount` of the payment token from `sender`, who must have approved the auction contract,
+        /// and returns why it failed if it did
+        fn receive_tokens(&mut self, sender: AccountId, amount: Balance) -> Result<(), AuctionError> {
+            let token = match self.get_payment_token() {
+                Some(token) => token,
+                None => {
+                    self.env().emit_event(Wrong_Currency {
+                        sender: Some(sender),
+                        payment_token: None,
+                    });
+                    return Err(AuctionError::WrongCurrency)
+                }
+            };
+            if !self.transfer_asset(Asset::Fungible { contract: token, amount }, sender, self.env().account_id()) {
+                self.env().emit_event(Token_Transfer_Failed {
+                    sender: Some(sender),
+                    amount,
+                });
+                return Err(AuctionError::TokenTransferFailed)
+            }
+            Ok(())
+        }
+
+        /// returns the house's share of every lot's proceeds, if it takes one
+        #[ink(message)]
+        fn get_platform_fee(&self) -> Option<Split> {
+            *self.platform_fee
+        }
+
+        /// returns the royalty splits taken from every lot's proceeds
+        #[ink(message)]
+        fn get_royalties(&self) -> Vec<Split> {
+            (0..*self.royalty_count).filter_map(|index| self.royalties.get(&index).cloned()).collect()
+        }
+
+        /// returns the minimum acceptable next bid on the lot i.e just above the starting_price until the first bid
+        /// and the highest bid plus the minimum increment after that, for dutch lots the price decayed from the
+        /// starting price towards the floor price
+        #[ink(message)]
+        fn get_current_asking_price(&self, auction_id: AuctionId) -> Balance {
+            if self.get_kind(auction_id) == AuctionKind::Dutch {
+                return self.get_dutch_price(auction_id)
+            }
+            // any unit price above the starting price can still win units
+            if self.get_kind(auction_id) == AuctionKind::MultiUnit {
+                return self.get_starting_price(auction_id).saturating_add(1)
+            }
+            let highest_bid = self.get_highest_bid(auction_id);
+            // sealed reveals only have to beat the leader
+            if highest_bid == 0 || self.get_kind(auction_id) == AuctionKind::Sealed {
+                return core::cmp::max(highest_bid, self.get_starting_price(auction_id)).saturating_add(1)
+            }
+            highest_bid.saturating_add(self.get_increment_over(highest_bid))
+        }
+
+        /// returns the house's minimum increment
+        #[ink(message)]
+        fn get_min_increment(&self) -> Increment {
+            *self.min_increment
+        }
+
+        /// returns how much a bid has to beat `highest_bid` by
+        fn get_increment_over(&self, highest_bid: Balance) -> Balance {
+            let increment = match self.get_min_increment() {
+                Increment::Absolute(increment) => increment,
+                Increment::BasisPoints(bps) => self.basis_points_of(highest_bid, bps),
+            };
+            core::cmp::max(increment, 1)
+        }
+
+        /// returns the dutch lot's floor price
+        #[ink(message)]
+        fn get_floor_price(&self, auction_id: AuctionId) -> Balance {
+            *self.floor_price.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns how the dutch lot's asking price falls
+        #[ink(message)]
+        fn get_price_decay(&self, auction_id: AuctionId) -> PriceDecay {
+            *self.price_decay.get(&auction_id).unwrap_or(&PriceDecay::Linear)
+        }
+
+        /// returns the dutch lot's asking price at the current time
+        fn get_dutch_price(&self, auction_id: AuctionId) -> Balance {
+            let starting_price = self.get_starting_price(auction_id);
+            let created_time = self.get_created_time(auction_id);
+            let duration = self.get_end_time(auction_id).saturating_sub(created_time);
+            let mut elapsed = core::cmp::min(self.get_time().saturating_sub(created_time), duration);
+            if duration == 0 {
+                return self.get_floor_price(auction_id)
+            }
+            // step decay holds the price for each whole interval
+            if let PriceDecay::Step(interval) = self.get_price_decay(auction_id) {
+                if interval > 0 {
+                    elapsed -= elapsed % interval;
+                }
+            }
+            let total_drop = starting_price - self.get_floor_price(auction_id);
+            starting_price - self.fraction_of(total_drop, elapsed, duration)
+        }
+
+        /// returns the lot's highestBidder.
+        #[ink(message)]
+        fn get_highest_bidder(&self, auction_id: AuctionId) -> AccountId {
+            *self.highest_bidder.get(&auction_id).unwrap_or(&AccountId::from([0x0; 32]))
+        }
+
+        /// returns the AccountId of the lot's beneficiary
+        #[ink(message)]
+        fn get_beneficiary(&self, auction_id: AuctionId) -> AccountId {
+            *self.beneficiary.get(&auction_id).unwrap_or(&AccountId::from([0x0; 32]))
+        }
+
+        /// returns whether the lot's auction ended
+        #[ink(message)]
+        fn is_ended(&self, auction_id: AuctionId) -> bool {
+            *self.ended.get(&auction_id).unwrap_or(&false)
+        }
+
+        /// returns who may bid on the lot
+        #[ink(message)]
+        fn get_eligibility(&self, auction_id: AuctionId) -> Eligibility {
+            *self.eligibility.get(&auction_id).unwrap_or(&Eligibility::Open)
+        }
+
+        /// returns whether the account may currently bid on the lot
+        #[ink(message)]
+        fn is_eligible(&self, auction_id: AuctionId, id: AccountId) -> bool {
+            match self.get_eligibility(auction_id) {
+                Eligibility::Open => true,
+                Eligibility::Allowlist => *self.allowlist.get(&(auction_id, id)).unwrap_or(&false),
+                Eligibility::MerkleRoot(root) => self.proven.get(&(auction_id, id)) == Some(&root),
+            }
+        }
+
+        /// returns whether the lot was cancelled by its beneficiary
+        #[ink(message)]
+        fn is_cancelled(&self, auction_id: AuctionId) -> bool {
+            *self.cancelled.get(&auction_id).unwrap_or(&false)
+        }
+
+        /// returns when the beneficiary may cancel the lot
+        #[ink(message)]
+        fn get_cancel_policy(&self, auction_id: AuctionId) -> CancelPolicy {
+            *self.cancel_policy.get(&auction_id).unwrap_or(&CancelPolicy::BeforeBids)
+        }
+
+        /// returns the sender's withdrawl balance
+        #[ink(message)]
+        fn my_withdrawl_balance(&self) -> Balance {
+            *self.pending_returns.get(&self.env().caller()).unwrap_or(&0)
+        }
+
+        /// returns a given accounts withdraw balance
+         #[ink(message)]
+        fn curr_withdrawl_amount(&self, id: AccountId) -> Balance {
+            *self.pending_returns.get(&id).unwrap_or(&0)
+        }
+
+        /// returns the bidding rules of the lot
+        #[ink(message)]
+        fn get_kind(&self, auction_id: AuctionId) -> AuctionKind {
+            *self.kind.get(&auction_id).unwrap_or(&AuctionKind::English)
+        }
+
+        /// returns the time when a sealed lot's reveal phase closes
+        #[ink(message)]
+        fn get_reveal_end_time(&self, auction_id: AuctionId) -> Timestamp {
+            *self.reveal_end_time.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the basis points of the deposit a sealed lot's bidder forfeits by not revealing
+        #[ink(message)]
+        fn get_unrevealed_penalty(&self, auction_id: AuctionId) -> u32 {
+            *self.unrevealed_penalty.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the commitment the given account made on a sealed lot, if it has not been revealed yet
+        #[ink(message)]
+        fn get_commitment(&self, auction_id: AuctionId, id: AccountId) -> Option<Hash> {
+            self.commitments.get(&(auction_id, id)).cloned()
+        }
+
+        /// returns the number of accounts that committed to a bid on a sealed lot, revealed or not
+        #[ink(message)]
+        fn get_commitment_count(&self, auction_id: AuctionId) -> u32 {
+            *self.commitment_count.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the deposit the given account has locked on a sealed lot
+        #[ink(message)]
+        fn get_deposit(&self, auction_id: AuctionId, id: AccountId) -> Balance {
+            *self.deposits.get(&(auction_id, id)).unwrap_or(&0)
+        }
+
+        /// returns the commitment to send with `commit_bid` for the given amount and salt.
+        /// note that calling this on a remote node discloses the amount to that node
+        #[ink(message)]
+        fn compute_commitment(&self, amount: Balance, salt: Hash) -> Hash {
+            Hash::from(scale::Encode::using_encoded(&(amount, salt), ink_core::hash::blake2_256))
+        }
+
+        /// returns how close to end_time a bid has to be made to extend the lot
+        #[ink(message)]
+        fn get_extension_window(&self, auction_id: AuctionId) -> Timestamp {
+            *self.extension_window.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns how far a bid close to end_time pushes the lot's end_time out
+        #[ink(message)]
+        fn get_extension_length(&self, auction_id: AuctionId) -> Timestamp {
+            *self.extension_length.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the cap on the total time the lot's end_time can be pushed out
+        #[ink(message)]
+        fn get_max_extension(&self, auction_id: AuctionId) -> Timestamp {
+            *self.max_extension.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the time the lot's end_time has been pushed out so far
+        #[ink(message)]
+        fn get_total_extension(&self, auction_id: AuctionId) -> Timestamp {
+            *self.total_extension.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// pushes the lot's end_time out if a bid by `bidder` landed within its extension window
+        fn extend_if_sniped(&mut self, auction_id: AuctionId, bidder: AccountId) {
+            let now = self.get_time();
+            let end_time = self.get_end_time(auction_id);
+            if now > end_time || end_time - now > self.get_extension_window(auction_id) {
+                return
+            }
+
+            let total_extension = self.get_total_extension(auction_id);
+            let extension = core::cmp::min(self.get_extension_length(auction_id),
+                                           self.get_max_extension(auction_id).saturating_sub(total_extension));
+            if extension == 0 {
+                return
+            }
+
+            let end_time = end_time.saturating_add(extension);
+            self.end_time.insert(auction_id, end_time);
+            self.total_extension.insert(auction_id, total_extension + extension);
+
+            self.env().emit_event(Auction_Extended {
+                auction_id,
+                bidder: Some(bidder),
+                end_time,
+                total_extension: total_extension + extension,
+            });
+        }
+
+        /// returns whether the sealed lot is accepting commitments
+        fn in_commit_phase(&self, auction_id: AuctionId) -> bool {
+            self.get_time() <= self.get_end_time(auction_id)
+        }
+
+        /// returns whether the sealed lot is accepting reveals
+        fn in_reveal_phase(&self, auction_id: AuctionId) -> bool {
+            !self.in_commit_phase(auction_id) && self.get_time() <= self.get_reveal_end_time(auction_id)
+        }
+
+        /// returns the time after which non-beneficiaries can end the lot's auction,
+        /// sealed lots can only be ended once their reveal phase is over
+        fn get_end_allowed_time(&self, auction_id: AuctionId) -> Timestamp {
+            match self.get_kind(auction_id) {
+                AuctionKind::Sealed => self.get_reveal_end_time(auction_id),
+                AuctionKind::English | AuctionKind::Dutch | AuctionKind::MultiUnit | AuctionKind::Candle =>
+                    self.get_end_time(auction_id),
+            }
+        }
+
+        /// returns whether a non-beneficiaries can end the lot's auction, which needs bidding to have opened as well
+        #[ink(message)]
+        fn time_end_allowed(&self, auction_id: AuctionId) -> bool{
+            self.has_started(auction_id) && self.get_time() > self.get_end_allowed_time(auction_id)
+        }
+
+        /// returns the time when bidding opens on the lot
+        #[ink(message)]
+        fn get_start_time(&self, auction_id: AuctionId) -> Timestamp {
+            *self.start_time.get(&auction_id).unwrap_or(&self.get_created_time(auction_id))
+        }
+
+        /// returns the amount of time left in milliseconds (or blocks) until bidding opens on the lot
+        #[ink(message)]
+        fn get_time_until_start(&self, auction_id: AuctionId) -> Timestamp {
+            self.get_start_time(auction_id).saturating_sub(self.get_time())
+        }
+
+        /// returns whether bidding has opened on the lot
+        #[ink(message)]
+        fn has_started(&self, auction_id: AuctionId) -> bool {
+            self.get_time() >= self.get_start_time(auction_id)
+        }
+
+        /// returns the current block timestamp, or the current block number in a house measuring time in blocks
+        #[ink(message)]
+        fn get_time(&self) -> Timestamp{
+            match self.get_time_unit() {
+                TimeUnit::Milliseconds => self.env().block_timestamp(),
+                TimeUnit::Blocks => u64::from(self.env().block_number()),
+            }
+        }
+
+        /// returns what the house's times and windows are measured in
+        #[ink(message)]
+        fn get_time_unit(&self) -> TimeUnit {
+            *self.time_unit
+        }
+
+        /// returns the time when the lot was created
+        #[ink(message)]
+        fn get_created_time(&self, auction_id: AuctionId) -> Timestamp{
+            *self.created_time.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the time when the lot is allowed to be ended by non-beneficiaries,
+        /// including any anti-sniping extension
+        #[ink(message)]
+        fn get_end_time(&self, auction_id: AuctionId) -> Timestamp{
+            *self.end_time.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the amount of time left in milliseconds (or blocks) until non-beneficiaries can end the lot's
+        /// auction
+        #[ink(message)]
+        fn get_time_left(&self, auction_id: AuctionId) -> Timestamp{
+            self.get_end_allowed_time(auction_id).saturating_sub(self.get_time())
+        }
+
+
+        /// this function can be called to end the lot's auction and returns an error saying why if the call was not successful
+        /// note that you can't end a lot more than once, the lot's beneficiary can always end it and non-beneficiaries
+        /// can end it after the end_time. Ending ends bidding but withdrawing is still allowed. The highest bid will
+        /// be added to the beneficiary's withdraw balance, unless it does not meet the lot's reserve price in which case
+        /// it is added to the highest bidder's withdraw balance. An escrowed asset is handed over to the winner,
+        /// or back to the beneficiary if the lot did not sell. Sealed lots can only be ended by anyone once their reveal
+        /// phase is over, the highest valid reveal wins. Under the second-price rule only the clearing price goes to
+        /// the beneficiary and the rest of the winning bid is added to the winner's withdraw balance
+        #[ink(message)]
+        fn end(&mut self, auction_id: AuctionId) -> Result<(), AuctionError> {
+
+            //making sure the lot exists
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                });
+                return Err(AuctionError::NoSuchAuction)
+            }
+            //making sure the lot was not already ended
+            else if self.is_ended(auction_id) {
+                self.env().emit_event(Already_Ended {
+                    auction_id,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return Err(AuctionError::AlreadyEnded)
+            }
+            //only allowed to end if you are the benificiary or the time is past end_time
+            //the beneficiary of a sealed lot has to wait for the reveals as well, that of a candle lot for the ending period
+            else if (self.get_beneficiary(auction_id) != self.env().caller()
+                     || self.get_kind(auction_id) == AuctionKind::Sealed
+                     || self.get_kind(auction_id) == AuctionKind::Candle)
+                    && !self.time_end_allowed(auction_id) {
+                self.env().emit_event(Not_Beneficiary {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    beneficiary: Some(self.get_beneficiary(auction_id)),
+                });
+                return Err(AuctionError::EndTooEarly)
+            }
+
+
+            self.settle(auction_id);
+            Ok(())
+        }
+
+        /// marks the lot as ended, pays out its highest bid and hands over its escrowed asset. Multi-unit lots are
+        /// paid out afterwards by `settle_units`, candle lots once their close is drawn by `draw_candle` and bonded
+        /// lots once the winner confirms the purchase
+        fn settle(&mut self, auction_id: AuctionId) {
+            self.ended.insert(auction_id, true);
+            if self.get_kind(auction_id) == AuctionKind::Candle {
+                // the caller of `end` picks its block, so the close is drawn from the randomness of the next one
+                let end_block = self.env().block_number();
+                self.candle_end_block.insert(auction_id, end_block);
+                self.env().emit_event(Candle_Ended {
+                    auction_id,
+                    end_block,
+                });
+                return
+            }
+            self.env().emit_event(Ended {
+                auction_id,
+                highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                highest_bid: self.get_highest_bid(auction_id),
+            });
+            if self.get_kind(auction_id) == AuctionKind::MultiUnit {
+                return
+            }
+            //the winner of a bonded lot has to confirm the purchase before it is paid out, unless the reserve
+            //was missed and the bid is returned anyway
+            if self.get_bid_bond(auction_id) > 0 && self.get_highest_bid(auction_id) > 0
+                && self.is_reserve_met(auction_id) {
+                self.await_confirmation(auction_id);
+                return
+            }
+            self.pay_out(auction_id);
+            self.deliver_asset_of(auction_id);
+        }
+
+        /// pays out the lot's highest bid, or returns it to the bidder if the reserve was not met
+        fn pay_out(&mut self, auction_id: AuctionId) {
+            if !self.is_reserve_met(auction_id) {
+                self.add_pending_returns(self.get_highest_bidder(auction_id), self.get_locked_amount(auction_id));
+
+                // emit event
+                self.env().emit_event(Reserve_Not_Met {
+                    auction_id,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return
+            }
+
+            //add the clearing price to the beneficiary pending returns and the winner's excess
+            //(including any unused proxy escrow) to theirs
+            let winning_bid = self.get_highest_bid(auction_id);
+            let clearing_price = self.get_clearing_price(auction_id);
+            if self.escrow_arbitrator.get(&auction_id).is_some() {
+                self.hold_in_escrow(auction_id, clearing_price);
+            }
+            else {
+                self.distribute_proceeds(auction_id, clearing_price);
+            }
+            self.add_pending_returns(self.get_highest_bidder(auction_id),
+                                     self.get_locked_amount(auction_id) - clearing_price);
+
+
+            // emit event
+            self.env().emit_event(Settled {
+                auction_id,
+                winner: Some(self.get_highest_bidder(auction_id)),
+                winning_bid,
+                clearing_price,
+            });
+        }
+
+
+        /// splits the price paid for the lot between the platform fee, the royalties and the beneficiary, who also
+        /// gets the rounding remainders, and adds every share to its recipient's withdraw balance
+        fn distribute_proceeds(&mut self, auction_id: AuctionId, proceeds: Balance) {
+            let mut remainder = proceeds;
+            let splits = self.get_platform_fee().into_iter().chain(self.get_royalties());
+            for split in splits.collect::<Vec<Split>>() {
+                let amount = self.basis_points_of(proceeds, split.basis_points);
+                remainder -= amount;
+                self.add_pending_returns(split.recipient, amount);
+                self.env().emit_event(Payout {
+                    auction_id,
+                    recipient: Some(split.recipient),
+                    amount,
+                });
+            }
+
+            let beneficiary = self.get_beneficiary(auction_id);
+            self.add_pending_returns(beneficiary, remainder);
+            self.env().emit_event(Payout {
+                auction_id,
+                recipient: Some(beneficiary),
+                amount: remainder,
+            });
+        }
+
+        /// To call this funciton, money must be sent to the contract. Bids under the lot's curent asking price are
+        /// added to the senders withdraw balance. Bids higher than the asking price are locked in the contract
+        /// until either the bid is trumped in which case the bid is returned or the lot's bidding period is ended
+        /// in which case the amount is transfered to the beificiary. In a house whose rejection policy is
+        /// `RejectionPolicy::Revert` a rejected bid is sent back instead of being added to the withdraw balance
+        #[ink(message)]
+        fn bid(&mut self, auction_id: AuctionId) -> Result<(), AuctionError> {
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
+            //the amount transfered to the contract ie the bid amount
+            let amount: Balance = self.env().transferred_balance();
+            let sender = self.env().caller();
+            let result = self.place_bid(auction_id, sender, amount);
+            self.revert_if_rejected(sender, amount, result)
+        }
+
+        /// Works like `bid` in a house with a payment token, `amount` tokens are pulled from the sender
+        /// who must have approved the auction contract to transfer them
+        #[ink(message)]
+        fn bid_tokens(&mut self, auction_id: AuctionId, amount: Balance) -> Result<(), AuctionError> {
+            let sender = self.env().caller();
+            self.receive_tokens(sender, amount)?;
+            let result = self.place_bid(auction_id, sender, amount);
+            self.revert_if_rejected(sender, amount, result)
+        }
+
+        /// Works like `bid` but the bid is the money sent plus `from_pending` taken from the sender's withdraw
+        /// balance, so an outbid bidder can top up their returned bid instead of withdrawing and sending it again.
+        /// A rejected bid goes back to the withdraw balance in full, except for the money sent under
+        /// `RejectionPolicy::Revert`
+        #[ink(message)]
+        fn bid_with_pending(&mut self, auction_id: AuctionId, from_pending: Balance) -> Result<(), AuctionError> {
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
+            let transferred: Balance = self.env().transferred_balance();
+            let sender = self.env().caller();
+            let available = self.curr_withdrawl_amount(sender);
+
+            if from_pending > available {
+                self.add_pending_returns(sender, transferred);
+                self.env().emit_event(Insufficient_Pending_Returns {
+                    auction_id,
+                    bidder: Some(sender),
+                    requested: from_pending,
+                    available,
+                });
+                return self.revert_if_rejected(sender, transferred, Err(AuctionError::InsufficientPendingReturns))
+            }
+
+            self.pending_returns.insert(sender, available - from_pending);
+            let result = self.place_bid(auction_id, sender, transferred + from_pending);
+            self.revert_if_rejected(sender, transferred, result)
+        }
+
+        /// bids `amount` on the lot purely out of the sender's withdraw balance
+        #[ink(message)]
+        fn bid_from_pending(&mut self, auction_id: AuctionId, amount: Balance) -> Result<(), AuctionError> {
+            self.bid_with_pending(auction_id, amount)
+        }
+
+        /// returns what happens to the money sent with a rejected bid
+        #[ink(message)]
+        fn get_rejection_policy(&self) -> RejectionPolicy {
+            *self.rejection_policy
+        }
+
+        /// sends the `transferred` part of a rejected call, which was added to the sender's withdraw balance,
+        /// back to the sender if the house reverts rejected bids. It stays in the withdraw balance if sending fails
+        fn revert_if_rejected(&mut self, sender: AccountId, transferred: Balance,
+                              result: Result<(), AuctionError>) -> Result<(), AuctionError> {
+            if result.is_ok() || transferred == 0 || self.get_rejection_policy() == RejectionPolicy::CreditPending {
+                return result
+            }
+
+            let available = self.curr_withdrawl_amount(sender);
+            self.pending_returns.insert(sender, available.saturating_sub(transferred));
+            let returned = match self.get_payment_token() {
+                Some(token) => self.transfer_asset(Asset::Fungible { contract: token, amount: transferred },
+                                                   self.env().account_id(), sender),
+                None => self.env().transfer(sender, transferred).is_ok(),
+            };
+            if !returned {
+                self.pending_returns.insert(sender, available);
+            }
+            result
+        }
+
+        /// places a bid of `amount` by `sender` on the lot, `amount` must already be held by the contract
+        fn place_bid(&mut self, auction_id: AuctionId, sender: AccountId, amount: Balance) -> Result<(), AuctionError> {
+            let previous_highest_bid = self.get_highest_bid(auction_id);
+
+
+            //if the lot does not exist, return an error
+            if !self.exists(auction_id) {
+                self.add_pending_returns(sender, amount);
+                // emit event
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(sender),
+                });
+                return Err(AuctionError::NoSuchAuction)
+            }
+            //sealed lots take commitments and multi-unit lots unit bids instead of open bids
+            else if self.get_kind(auction_id) == AuctionKind::Sealed || self.get_kind(auction_id) == AuctionKind::MultiUnit {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::WrongKind);
+                // emit event
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(sender),
+                    kind: self.get_kind(auction_id),
+                });
+                return Err(AuctionError::WrongAuctionKind)
+            }
+            //if the bid is made after the voting closes, or after the ending period of a candle lot, return an error
+            else if self.is_ended(auction_id)
+                    || (self.get_kind(auction_id) == AuctionKind::Candle && self.get_time() >= self.get_end_time(auction_id)) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::Ended);
+                // emit event
+                self.env().emit_event(No_More_Bidding {
+                    auction_id,
+                    is_ended: self.is_ended(auction_id),
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return Err(AuctionError::BiddingClosed)
+            }
+            //if bidding has not opened yet, return an error
+            else if !self.has_started(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::NotStarted);
+                // emit event
+                self.env().emit_event(Not_Started {
+                    auction_id,
+                    sender: Some(sender),
+                    start_time: self.get_start_time(auction_id),
+                });
+                return Err(AuctionError::NotStarted)
+            }
+            //if the sender may not bid on the lot, return an error
+            else if !self.is_eligible(auction_id, sender) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::NotEligible);
+                // emit event
+                self.env().emit_event(Not_Eligible {
+                    auction_id,
+                    bidder: Some(sender),
+                    eligibility: self.get_eligibility(auction_id),
+                });
+                return Err(AuctionError::NotEligible)
+            }
+            //if the lot requires a bond the sender has not registered, return an error
+            else if !self.has_bond(auction_id, sender) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::NoBond);
+                // emit event
+                self.env().emit_event(Bond_Required {
+                    auction_id,
+                    bidder: Some(sender),
+                    bond: self.get_bid_bond(auction_id),
+                });
+                return Err(AuctionError::BondRequired)
+            }
+            //dutch lots are sold to the first bid covering the asking price
+            else if self.get_kind(auction_id) == AuctionKind::Dutch {
+                return self.buy_dutch(auction_id, sender, amount)
+            }
+            //a bid covering the buy-now price wins the lot instantly
+            else if self.get_buy_now_price(auction_id).map_or(false, |price| amount >= price) {
+                return self.buy_now(auction_id, sender, amount)
+            }
+            // if the bid is not higher than the starting price, then return an error
+            else if amount <= self.get_starting_price(auction_id) {
+                //value is to low so allow the sender to collect the funds
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::LowerThanStartingPrice);
+
+                // emit event
+                self.env().emit_event(Failed_Bid_Lower_Than_Starting_Price {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: amount,
+                    starting_price: self.get_starting_price(auction_id),
+                });
+                return Err(AuctionError::LowerThanStartingPrice)
+            }
+            // if the bid is not higher than the current highest, then return an error
+            else if amount <= previous_highest_bid {
+                //value is to low so allow the sender to collect the funds
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::LowerThanHighestBid);
+
+                // emit event
+                self.env().emit_event(Failed_Bid_Lower_Than_Highest_Bid {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: amount,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return Err(AuctionError::LowerThanHighestBid)
+            }
+            // if the bid does not beat the current highest by the minimum increment, then return an error
+            else if amount < self.get_current_asking_price(auction_id) {
+                //value is to low so allow the sender to collect the funds
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::BelowMinIncrement);
+
+                // emit event
+                self.env().emit_event(Failed_Bid_Below_Min_Increment {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: amount,
+                    min_bid: self.get_current_asking_price(auction_id),
+                });
+                return Err(AuctionError::BelowMinIncrement)
+            }
+
+
+            // a proxy bid of the highest bidder outbids anything up to its maximum
+            else if amount <= self.get_proxy_max(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::LowerThanHighestBid);
+                self.defend_with_proxy(auction_id, sender, amount);
+
+                // emit event
+                self.env().emit_event(Failed_Bid_Lower_Than_Highest_Bid {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: amount,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return Err(AuctionError::LowerThanHighestBid)
+            }
+
+
+            // Sending back the money by simply using a command is a security risk
+            // because it could execute an untrusted contract.
+            // It is safer to let the recipients withdraw their money themselves.
+            // look up "reentracy attack"
+            // add_pending_returns keeps any other pending money the highest bidder had, e.g. from other lots
+            let previous_highest_bidder = self.get_highest_bidder(auction_id);
+            if self.get_kind(auction_id) == AuctionKind::Candle {
+                // outbid candle bids can still win at settlement so they stay locked,
+                // a new bid covers the bidder's earlier ones though
+                self.add_pending_returns(sender, self.get_candle_locked(auction_id, sender));
+                self.candle_locked.insert((auction_id, sender), amount);
+                let slot = self.get_candle_slot(auction_id);
+                self.candle_leaders.insert((auction_id, slot), sender);
+                self.candle_bids.insert((auction_id, slot), amount);
+            } else {
+                self.add_pending_returns(previous_highest_bidder, self.get_locked_amount(auction_id));
+            }
+            self.proxy_max.insert(auction_id, 0);
+
+
+            // change the highest bidder to the new highest bidder
+            self.second_highest_bid.insert(auction_id, previous_highest_bid);
+            self.runner_up.insert(auction_id, previous_highest_bidder);
+            self.highest_bid.insert(auction_id, amount);
+            self.highest_bidder.insert(auction_id, sender);
+            self.log_bid(auction_id, sender, amount, BidStatus::Accepted);
+
+            // emit event
+            self.env().emit_event(New_Highest_Bid {
+                auction_id,
+                previous_highest_bidder: Some(previous_highest_bidder),
+                previous_highest_bid: previous_highest_bid,
+                highest_bidder: Some(sender),
+                highest_bid: amount,
+                automatic: false,
+            });
+
+            self.extend_if_sniped(auction_id, sender);
+            Ok(())
+        }
+
+
+        /// To call this funciton, money must be sent to the contract as the maximum the sender is willing to pay.
+        /// The contract bids on the english lot on the sender's behalf, in minimum increments, whenever they are
+        /// outbid until the maximum is reached. Once outbid or when the lot ends the unused part of the maximum is
+        /// added to the sender's withdraw balance. Events only ever show the bid placed, not the maximum. If the
+        /// sender already holds the highest bid, the transferred money is added to their maximum
+        #[ink(message)]
+        fn bid_proxy(&mut self, auction_id: AuctionId) -> Result<(), AuctionError> {
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
+            let max: Balance = self.env().transferred_balance();
+            let sender = self.env().caller();
+            let result = self.place_proxy_bid(auction_id, sender, max);
+            self.revert_if_rejected(sender, max, result)
         }
 
-        /// returns the block timestamp when the auction was created
+        /// Works like `bid_proxy` in a house with a payment token, the `max` tokens are pulled from the sender
+        /// who must have approved the auction contract to transfer them
         #[ink(message)]
-        fn get_created_time(&self) -> Timestamp{
-            *self.created_time
+        fn bid_proxy_tokens(&mut self, auction_id: AuctionId, max: Balance) -> Result<(), AuctionError> {
+            let sender = self.env().caller();
+            self.receive_tokens(sender, max)?;
+            let result = self.place_proxy_bid(auction_id, sender, max);
+            self.revert_if_rejected(sender, max, result)
+        }
+
+        /// places a proxy bid by `sender` on the lot, `max` must already be held by the contract
+        fn place_proxy_bid(&mut self, auction_id: AuctionId, sender: AccountId, max: Balance)
+                           -> Result<(), AuctionError> {
+            let previous_highest_bid = self.get_highest_bid(auction_id);
+
+            if !self.exists(auction_id) {
+                self.add_pending_returns(sender, max);
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(sender),
+                });
+                return Err(AuctionError::NoSuchAuction)
+            }
+            else if self.get_kind(auction_id) != AuctionKind::English {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::WrongKind);
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(sender),
+                    kind: self.get_kind(auction_id),
+                });
+                return Err(AuctionError::WrongAuctionKind)
+            }
+            else if self.is_ended(auction_id) {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::Ended);
+                self.env().emit_event(No_More_Bidding {
+                    auction_id,
+                    is_ended: self.is_ended(auction_id),
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: previous_highest_bid,
+                });
+                return Err(AuctionError::BiddingClosed)
+            }
+            else if !self.has_started(auction_id) {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::NotStarted);
+                self.env().emit_event(Not_Started {
+                    auction_id,
+                    sender: Some(sender),
+                    start_time: self.get_start_time(auction_id),
+                });
+                return Err(AuctionError::NotStarted)
+            }
+            else if !self.is_eligible(auction_id, sender) {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::NotEligible);
+                self.env().emit_event(Not_Eligible {
+                    auction_id,
+                    bidder: Some(sender),
+                    eligibility: self.get_eligibility(auction_id),
+                });
+                return Err(AuctionError::NotEligible)
+            }
+            else if !self.has_bond(auction_id, sender) {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::NoBond);
+                self.env().emit_event(Bond_Required {
+                    auction_id,
+                    bidder: Some(sender),
+                    bond: self.get_bid_bond(auction_id),
+                });
+                return Err(AuctionError::BondRequired)
+            }
+            // the highest bidder raises their maximum without changing the bid
+            else if previous_highest_bid > 0 && self.get_highest_bidder(auction_id) == sender {
+                self.proxy_max.insert(auction_id, self.get_locked_amount(auction_id) + max);
+                self.env().emit_event(Proxy_Max_Raised {
+                    auction_id,
+                    bidder: Some(sender),
+                });
+                return Ok(())
+            }
+            else if max < self.get_current_asking_price(auction_id) {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::BelowMinIncrement);
+                self.env().emit_event(Failed_Bid_Below_Min_Increment {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: max,
+                    min_bid: self.get_current_asking_price(auction_id),
+                });
+                return Err(AuctionError::BelowMinIncrement)
+            }
+            // the highest bidder's proxy wins ties
+            else if max <= self.get_proxy_max(auction_id) {
+                self.add_pending_returns(sender, max);
+                self.log_bid(auction_id, sender, max, BidStatus::LowerThanHighestBid);
+                self.defend_with_proxy(auction_id, sender, max);
+                self.env().emit_event(Failed_Bid_Lower_Than_Highest_Bid {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: max,
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
+                });
+                return Err(AuctionError::LowerThanHighestBid)
+            }
+
+            // bid just enough to beat whatever the highest bidder has locked
+            let previous_highest_bidder = self.get_highest_bidder(auction_id);
+            let previous_locked = self.get_locked_amount(auction_id);
+            let mut price = self.get_current_asking_price(auction_id);
+            if previous_highest_bid > 0 {
+                price = core::cmp::max(price, previous_locked.saturating_add(self.get_increment_over(previous_locked)));
+            }
+            let price = core::cmp::min(price, max);
+
+            self.add_pending_returns(previous_highest_bidder, previous_locked);
+            self.second_highest_bid.insert(auction_id, previous_locked);
+            self.runner_up.insert(auction_id, previous_highest_bidder);
+            self.highest_bid.insert(auction_id, price);
+            self.highest_bidder.insert(auction_id, sender);
+            self.proxy_max.insert(auction_id, max);
+            // only the price is logged, the maximum stays hidden
+            self.log_bid(auction_id, sender, price, BidStatus::Automatic);
+
+            self.env().emit_event(New_Highest_Bid {
+                auction_id,
+                previous_highest_bidder: Some(previous_highest_bidder),
+                previous_highest_bid,
+                highest_bidder: Some(sender),
+                highest_bid: price,
+                automatic: true,
+            });
+
+            self.extend_if_sniped(auction_id, sender);
+            Ok(())
+        }
+
+        /// raises the highest bidder's proxy bid just enough to beat a challenger willing to pay
+        /// `challenger_max`, without going over the proxy's maximum
+        fn defend_with_proxy(&mut self, auction_id: AuctionId, challenger: AccountId, challenger_max: Balance) {
+            let highest_bidder = self.get_highest_bidder(auction_id);
+            let previous_highest_bid = self.get_highest_bid(auction_id);
+            let price = core::cmp::min(self.get_proxy_max(auction_id),
+                                       challenger_max.saturating_add(self.get_increment_over(challenger_max)));
+            if price <= previous_highest_bid {
+                return
+            }
+
+            self.second_highest_bid.insert(auction_id, challenger_max);
+            self.runner_up.insert(auction_id, challenger);
+            self.highest_bid.insert(auction_id, price);
+            self.log_bid(auction_id, highest_bidder, price, BidStatus::Automatic);
+
+            self.env().emit_event(New_Highest_Bid {
+                auction_id,
+                previous_highest_bidder: Some(highest_bidder),
+                previous_highest_bid,
+                highest_bidder: Some(highest_bidder),
+                highest_bid: price,
+                automatic: true,
+            });
+
+            self.extend_if_sniped(auction_id, highest_bidder);
+        }
+
+        /// buys the dutch lot for `sender` if `amount` covers the current asking price, the lot is settled at
+        /// the asking price and any overpayment is added to the sender's withdraw balance
+        fn buy_dutch(&mut self, auction_id: AuctionId, sender: AccountId, amount: Balance) -> Result<(), AuctionError> {
+            let asking_price = self.get_dutch_price(auction_id);
+
+            // the price no longer falls after end_time and the lot waits to be ended unsold
+            if self.time_end_allowed(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::Ended);
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(sender),
+                    time: self.get_time(),
+                });
+                return Err(AuctionError::BiddingClosed)
+            }
+            else if amount < asking_price {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::LowerThanAskingPrice);
+                self.env().emit_event(Failed_Bid_Lower_Than_Asking_Price {
+                    auction_id,
+                    attempted_bidder: Some(sender),
+                    attempted_bid: amount,
+                    asking_price,
+                });
+                return Err(AuctionError::LowerThanAskingPrice)
+            }
+
+            let previous_highest_bidder = self.get_highest_bidder(auction_id);
+            self.add_pending_returns(sender, amount - asking_price);
+            self.highest_bid.insert(auction_id, asking_price);
+            self.highest_bidder.insert(auction_id, sender);
+            self.log_bid(auction_id, sender, asking_price, BidStatus::Bought);
+
+            self.env().emit_event(New_Highest_Bid {
+                auction_id,
+                previous_highest_bidder: Some(previous_highest_bidder),
+                previous_highest_bid: 0,
+                highest_bidder: Some(sender),
+                highest_bid: asking_price,
+                automatic: false,
+            });
+            self.settle(auction_id);
+            Ok(())
+        }
+
+        /// sells the english lot to `sender` at its buy-now price, the previous highest bid and any overpayment
+        /// are added to their owners' withdraw balances and the lot is settled like `end` does
+        fn buy_now(&mut self, auction_id: AuctionId, sender: AccountId, amount: Balance) -> Result<(), AuctionError> {
+            let price = *self.buy_now_price.get(&auction_id).unwrap_or(&0);
+            let previous_highest_bidder = self.get_highest_bidder(auction_id);
+            self.add_pending_returns(previous_highest_bidder, self.get_locked_amount(auction_id));
+            self.add_pending_returns(sender, amount - price);
+            self.proxy_max.insert(auction_id, 0);
+
+            self.second_highest_bid.insert(auction_id, self.get_highest_bid(auction_id));
+            self.runner_up.insert(auction_id, previous_highest_bidder);
+            self.highest_bid.insert(auction_id, price);
+            self.highest_bidder.insert(auction_id, sender);
+            self.log_bid(auction_id, sender, price, BidStatus::Bought);
+
+            self.env().emit_event(Bought_Now {
+                auction_id,
+                buyer: Some(sender),
+                price,
+            });
+            self.settle(auction_id);
+            Ok(())
         }
 
-        /// returns the block timestamp when the auction is allowed to be ended by non-beneficiaries
+        /// To call this funciton, money must be sent to the contract as a deposit. Commits the sender to a sealed bid
+        /// on the lot, `commitment` being the hash returned by `compute_commitment(amount, salt)`. Committing again
+        /// replaces the previous commitment and adds to the deposit, which has to cover the amount that is revealed
         #[ink(message)]
-        fn get_end_time(&self) -> Timestamp{
-            *self.end_time
+        fn commit_bid(&mut self, auction_id: AuctionId, commitment: Hash) -> Result<(), AuctionError> {
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
+            let deposit: Balance = self.env().transferred_balance();
+            let sender = self.env().caller();
+            let result = self.place_commitment(auction_id, sender, commitment, deposit);
+            self.revert_if_rejected(sender, deposit, result)
         }
 
-        /// returns the amount of time left in mileseconds until non-beneficiaries can end the auction
+        /// Works like `commit_bid` in a house with a payment token, the `deposit` is pulled from the sender
+        /// who must have approved the auction contract to transfer it
         #[ink(message)]
-        fn get_time_left(&self) -> Timestamp{
-            self.get_end_time().saturating_sub(self.get_time())
+        fn commit_bid_tokens(&mut self, auction_id: AuctionId, commitment: Hash, deposit: Balance)
+                             -> Result<(), AuctionError> {
+            let sender = self.env().caller();
+            self.receive_tokens(sender, deposit)?;
+            let result = self.place_commitment(auction_id, sender, commitment, deposit);
+            self.revert_if_rejected(sender, deposit, result)
         }
 
+        /// commits `sender` to a sealed bid on the lot, `deposit` must already be held by the contract
+        fn place_commitment(&mut self, auction_id: AuctionId, sender: AccountId, commitment: Hash,
+                            deposit: Balance) -> Result<(), AuctionError> {
+
+            if !self.exists(auction_id) {
+                self.add_pending_returns(sender, deposit);
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(sender),
+                });
+                return Err(AuctionError::NoSuchAuction)
+            }
+            else if self.get_kind(auction_id) != AuctionKind::Sealed {
+                self.add_pending_returns(sender, deposit);
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(sender),
+                    kind: self.get_kind(auction_id),
+                });
+                return Err(AuctionError::WrongAuctionKind)
+            }
+            else if self.is_ended(auction_id) || !self.in_commit_phase(auction_id) {
+                self.add_pending_returns(sender, deposit);
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(sender),
+                    time: self.get_time(),
+                });
+                return Err(AuctionError::WrongPhase)
+            }
+            else if !self.is_eligible(auction_id, sender) {
+                self.add_pending_returns(sender, deposit);
+                self.env().emit_event(Not_Eligible {
+                    auction_id,
+                    bidder: Some(sender),
+                    eligibility: self.get_eligibility(auction_id),
+                });
+                return Err(AuctionError::NotEligible)
+            }
+
+            if self.get_commitment(auction_id, sender).is_none() {
+                let count = self.get_commitment_count(auction_id);
+                self.commitment_count.insert(auction_id, count + 1);
+            }
+            let total_deposit = self.get_deposit(auction_id, sender) + deposit;
+            self.commitments.insert((auction_id, sender), commitment);
+            self.deposits.insert((auction_id, sender), total_deposit);
+
+            self.env().emit_event(Bid_Committed {
+                auction_id,
+                bidder: Some(sender),
+                deposit: total_deposit,
+            });
+            Ok(())
+        }
 
-        /// this function can be called to end the auction and returns a bool indicating whether the call was successful
-        /// note that you can't end the auction more than once, the beneficiary can always end the auction and non-beneficiaries
-        /// can end the auction after the end_time. Ending ends bidding but withdrawing is still allowed. The highest bid will
-        /// be added to the beneficiary's withdraw balance
+        /// Opens the sender's commitment on a sealed lot during its reveal phase. A reveal that is covered by the
+        /// deposit and beats both the starting price and the best reveal so far becomes the highest bid, the rest
+        /// of the deposit is added to the sender's withdraw balance. Otherwise the whole deposit is returned that way
         #[ink(message)]
-        fn end(&mut self) -> bool {
-            
-            //making sure the contract was not already ended
-            if *self.ended {
-                self.env().emit_event(Already_Ended {
-                    highest_bidder: Some(self.get_highest_bidder()),
-                    highest_bid: self.get_highest_bid(),
+        fn reveal_bid(&mut self, auction_id: AuctionId, amount: Balance, salt: Hash) -> bool {
+            let sender = self.env().caller();
+
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(sender),
                 });
                 return false
             }
-            //only allowed to end if you are the benificiary or the time is past end_time
-            else if self.beneficiary != self.env().caller() && !self.time_end_allowed() {
-                self.env().emit_event(Not_Beneficiary {
-                    sender: Some(self.env().caller()),
-                    beneficiary: Some(*self.beneficiary),
+            else if self.is_ended(auction_id) || !self.in_reveal_phase(auction_id) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(sender),
+                    time: self.get_time(),
+                });
+                return false
+            }
+            // the sender must have an open commitment matching the amount and salt
+            else if self.get_commitment(auction_id, sender) != Some(self.compute_commitment(amount, salt)) {
+                self.env().emit_event(Invalid_Reveal {
+                    auction_id,
+                    bidder: Some(sender),
                 });
                 return false
             }
 
+            let deposit = self.get_deposit(auction_id, sender);
+            self.commitments.remove(&(auction_id, sender));
+            self.deposits.remove(&(auction_id, sender));
+
+            let previous_highest_bid = self.get_highest_bid(auction_id);
+            let leading = amount <= deposit
+                && amount > self.get_starting_price(auction_id)
+                && amount > previous_highest_bid;
+
+            if !leading {
+                // a valid reveal below the leader can still be the runner-up
+                if amount <= deposit && amount > self.get_second_highest_bid(auction_id) {
+                    self.second_highest_bid.insert(auction_id, amount);
+                }
+                self.add_pending_returns(sender, deposit);
+                let status = if amount > deposit {
+                    self.env().emit_event(Reveal_Exceeds_Deposit {
+                        auction_id,
+                        bidder: Some(sender),
+                        amount,
+                        deposit,
+                    });
+                    BidStatus::ExceedsDeposit
+                } else if amount <= self.get_starting_price(auction_id) {
+                    BidStatus::LowerThanStartingPrice
+                } else {
+                    BidStatus::LowerThanHighestBid
+                };
+                self.log_bid(auction_id, sender, amount, status);
+                self.env().emit_event(Bid_Revealed {
+                    auction_id,
+                    bidder: Some(sender),
+                    amount,
+                    leading,
+                });
+                return true
+            }
+
+            // the displaced leader gets their bid back, the new leader keeps only the bid locked
+            let previous_highest_bidder = self.get_highest_bidder(auction_id);
+            self.add_pending_returns(previous_highest_bidder, previous_highest_bid);
+            self.add_pending_returns(sender, deposit - amount);
 
-            self.ended.set(true);
+            self.second_highest_bid.insert(auction_id, previous_highest_bid);
+            self.highest_bid.insert(auction_id, amount);
+            self.highest_bidder.insert(auction_id, sender);
+            self.log_bid(auction_id, sender, amount, BidStatus::Accepted);
+
+            self.env().emit_event(Bid_Revealed {
+                auction_id,
+                bidder: Some(sender),
+                amount,
+                leading,
+            });
+            self.env().emit_event(New_Highest_Bid {
+                auction_id,
+                previous_highest_bidder: Some(previous_highest_bidder),
+                previous_highest_bid,
+                highest_bidder: Some(sender),
+                highest_bid: amount,
+                automatic: false,
+            });
+            true
+        }
 
+        /// Once a sealed lot's reveal phase is over anyone can release the deposit of a bidder that never revealed.
+        /// The lot's `unrevealed_penalty` share of the deposit goes to the beneficiary's withdraw balance and the
+        /// rest to the bidder's. Deposits of a cancelled lot can be released right away and are returned in full
+        #[ink(message)]
+        fn release_unrevealed(&mut self, auction_id: AuctionId, bidder: AccountId) -> bool {
+            let cancelled = self.is_cancelled(auction_id);
+            if !self.exists(auction_id) {
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                });
+                return false
+            }
+            else if self.get_kind(auction_id) != AuctionKind::Sealed {
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    kind: self.get_kind(auction_id),
+                });
+                return false
+            }
+            else if !(cancelled || self.time_end_allowed(auction_id)) {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    time: self.get_time(),
+                });
+                return false
+            }
+            // only an open commitment has a deposit left to release
+            else if self.get_commitment(auction_id, bidder).is_none() {
+                self.env().emit_event(Invalid_Reveal {
+                    auction_id,
+                    bidder: Some(bidder),
+                });
+                return false
+            }
 
-            //add to the highest_bid to the beneficiary pending returns
-            let beneficiary_curr_pending = self.curr_withdrawl_amount(self.get_beneficiary());
-            self.pending_returns.insert(self.get_beneficiary(), 
-                                        beneficiary_curr_pending + *self.highest_bid);
+            let deposit = self.get_deposit(auction_id, bidder);
+            let forfeited = if cancelled {
+                0
+            } else {
+                self.basis_points_of(deposit, self.get_unrevealed_penalty(auction_id))
+            };
+            self.commitments.remove(&(auction_id, bidder));
+            self.deposits.remove(&(auction_id, bidder));
 
+            self.add_pending_returns(self.get_beneficiary(auction_id), forfeited);
+            self.add_pending_returns(bidder, deposit - forfeited);
 
-            // emit event
-            self.env().emit_event(Ended {
-                highest_bidder: Some(self.get_highest_bidder()),
-                highest_bid: self.get_highest_bid(),
+            self.env().emit_event(Deposit_Forfeited {
+                auction_id,
+                bidder: Some(bidder),
+                forfeited,
+                returned: deposit - forfeited,
             });
             true
         }
 
 
-        /// To call this funciton, money must be sent to the contract. Bids under the curent asking price are
-        /// added to the senders withdraw balance. Bids higher than the asking price are locked in the contract
-        /// until either the bid is trumped in which case the bid is returned or the bidding period is ended
-        /// in which case the amount is transfered to the beificiary
-        #[ink(message)] 
-        fn bid(&mut self) -> bool {
-            //the amount transfered to the contract ie the bid amount
-            let amount: Balance = self.env().transferred_balance();
-            let previous_highest_bid = self.get_highest_bid();
+        /// Bids on a multi-unit lot for `quantity` units, the unit price being the money sent divided by `quantity`
+        /// with the remainder added to the sender's withdraw balance. The money stays locked until the lot is
+        /// settled, see `create_multi_unit_auction`. `after` is a hint of the bid the new one is listed after, as
+        /// returned by `find_unit_bid_position`, so placing it doesn't have to walk the whole list
+        #[ink(message)]
+        fn bid_units(&mut self, auction_id: AuctionId, quantity: u32, after: Option<u32>) -> Result<(), AuctionError> {
+            if !self.accepts_native() {
+                return Err(AuctionError::WrongCurrency)
+            }
             let sender = self.env().caller();
-            let sender_curr_pending = self.curr_withdrawl_amount(sender);
+            let transferred: Balance = self.env().transferred_balance();
+            if quantity == 0 {
+                self.add_pending_returns(sender, transferred);
+                self.env().emit_event(Invalid_Quantity {
+                    auction_id,
+                    bidder: Some(sender),
+                    quantity,
+                    units: self.get_units(auction_id),
+                });
+                return self.revert_if_rejected(sender, transferred, Err(AuctionError::InvalidQuantity))
+            }
+            let unit_price = transferred / Balance::from(quantity);
+            self.add_pending_returns(sender, transferred % Balance::from(quantity));
+            let result = self.place_unit_bid(auction_id, sender, quantity, unit_price, after);
+            self.revert_if_rejected(sender, transferred, result)
+        }
 
-            
-                        //if the bid is made after the voting closes, return false
-            if *self.ended {
-                self.pending_returns.insert(sender, amount + sender_curr_pending);
-                // emit event
+        /// Works like `bid_units` in a house with a payment token, `quantity` times `unit_price` tokens are pulled
+        /// from the sender who must have approved the auction contract to transfer them
+        #[ink(message)]
+        fn bid_units_tokens(&mut self, auction_id: AuctionId, quantity: u32, unit_price: Balance,
+                            after: Option<u32>) -> Result<(), AuctionError> {
+            let sender = self.env().caller();
+            let amount = unit_price.saturating_mul(Balance::from(quantity));
+            self.receive_tokens(sender, amount)?;
+            let result = self.place_unit_bid(auction_id, sender, quantity, unit_price, after);
+            self.revert_if_rejected(sender, amount, result)
+        }
+
+        /// places a bid by `sender` for `quantity` units at `unit_price` on the lot, the bid's total must already
+        /// be held by the contract
+        fn place_unit_bid(&mut self, auction_id: AuctionId, sender: AccountId, quantity: u32, unit_price: Balance,
+                          after: Option<u32>) -> Result<(), AuctionError> {
+            let amount = unit_price.saturating_mul(Balance::from(quantity));
+
+            if !self.exists(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.env().emit_event(No_Such_Auction {
+                    auction_id,
+                    sender: Some(sender),
+                });
+                return Err(AuctionError::NoSuchAuction)
+            }
+            else if self.get_kind(auction_id) != AuctionKind::MultiUnit {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::WrongKind);
+                self.env().emit_event(Wrong_Auction_Kind {
+                    auction_id,
+                    sender: Some(sender),
+                    kind: self.get_kind(auction_id),
+                });
+                return Err(AuctionError::WrongAuctionKind)
+            }
+            else if self.is_ended(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::Ended);
                 self.env().emit_event(No_More_Bidding {
-                    is_ended: *self.ended,
-                    highest_bidder: Some(self.get_highest_bidder()),
-                    highest_bid: self.get_highest_bid(),
+                    auction_id,
+                    is_ended: self.is_ended(auction_id),
+                    highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                    highest_bid: self.get_highest_bid(auction_id),
                 });
-                return false
+                return Err(AuctionError::BiddingClosed)
             }
-            // if the bid is not higher than the starting price, then return false
-            else if amount <= self.get_starting_price() {
-                //value is to low so allow the sender to collect the funds
-                self.pending_returns.insert(sender, amount + sender_curr_pending);
-
-                // emit event
+            else if !self.has_started(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::NotStarted);
+                self.env().emit_event(Not_Started {
+                    auction_id,
+                    sender: Some(sender),
+                    start_time: self.get_start_time(auction_id),
+                });
+                return Err(AuctionError::NotStarted)
+            }
+            else if !self.is_eligible(auction_id, sender) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::NotEligible);
+                self.env().emit_event(Not_Eligible {
+                    auction_id,
+                    bidder: Some(sender),
+                    eligibility: self.get_eligibility(auction_id),
+                });
+                return Err(AuctionError::NotEligible)
+            }
+            else if quantity == 0 || quantity > self.get_units(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.env().emit_event(Invalid_Quantity {
+                    auction_id,
+                    bidder: Some(sender),
+                    quantity,
+                    units: self.get_units(auction_id),
+                });
+                return Err(AuctionError::InvalidQuantity)
+            }
+            else if unit_price <= self.get_starting_price(auction_id) {
+                self.add_pending_returns(sender, amount);
+                self.log_bid(auction_id, sender, amount, BidStatus::LowerThanStartingPrice);
                 self.env().emit_event(Failed_Bid_Lower_Than_Starting_Price {
+                    auction_id,
                     attempted_bidder: Some(sender),
-                    attempted_bid: amount,
-                    starting_price: *self.starting_price,
+                    attempted_bid: unit_price,
+                    starting_price: self.get_starting_price(auction_id),
+                });
+                return Err(AuctionError::LowerThanStartingPrice)
+            }
+
+            // link the bid in after the last one at the same or a higher unit price
+            let index = self.get_unit_bid_count(auction_id);
+            let mut bid = UnitBid { bidder: sender, quantity, unit_price, next: None };
+            match self.find_unit_bid_position(auction_id, unit_price, after) {
+                Some(previous) => {
+                    let mut previous_bid = self.get_unit_bid(auction_id, previous).expect("listed bids exist");
+                    bid.next = previous_bid.next;
+                    previous_bid.next = Some(index);
+                    self.unit_bids.insert((auction_id, previous), previous_bid);
+                }
+                None => {
+                    bid.next = self.unit_bid_head.get(&auction_id).cloned();
+                    self.unit_bid_head.insert(auction_id, index);
+                    self.highest_bid.insert(auction_id, unit_price);
+                    self.highest_bidder.insert(auction_id, sender);
+                }
+            }
+            self.unit_bids.insert((auction_id, index), bid);
+            self.unit_bid_count.insert(auction_id, index + 1);
+            self.log_bid(auction_id, sender, amount, BidStatus::Accepted);
+
+            self.env().emit_event(Unit_Bid_Placed {
+                auction_id,
+                bidder: Some(sender),
+                quantity,
+                unit_price,
+            });
+            Ok(())
+        }
+
+        /// returns the index of the bid a new bid at `unit_price` would be listed after on the multi-unit lot, None
+        /// if it would become the first. The search starts from `after` when that bid is ahead of the new one and
+        /// from the first bid otherwise
+        #[ink(message)]
+        fn find_unit_bid_position(&self, auction_id: AuctionId, unit_price: Balance, after: Option<u32>) -> Option<u32> {
+            let ahead = |index: &u32| self.get_unit_bid(auction_id, *index).map_or(false, |bid| bid.unit_price >= unit_price);
+            let mut current = match after.filter(ahead) {
+                Some(index) => index,
+                None => self.unit_bid_head.get(&auction_id).cloned().filter(ahead)?,
+            };
+            // earlier bids stay ahead of later ones at the same unit price
+            while let Some(next) = self.get_unit_bid(auction_id, current).and_then(|bid| bid.next).filter(ahead) {
+                current = next;
+            }
+            Some(current)
+        }
+
+        /// Settles an ended multi-unit lot by visiting at most `max_steps` bids, anyone can call it until it returns
+        /// true. The first pass finds the lowest winning unit price, the second adds the part of every bid that
+        /// was not spent on units at that price to the bidder's withdraw balance and finally the proceeds are paid
+        /// out like those of any lot. A cancelled lot sells nothing and returns every bid
+        #[ink(message)]
+        fn settle_units(&mut self, auction_id: AuctionId, max_steps: u32) -> bool {
+            if self.get_kind(auction_id) != AuctionKind::MultiUnit || !self.is_ended(auction_id) {
+                return false
+            }
+
+            let mut settlement = self.get_unit_settlement(auction_id);
+            let units = if self.is_cancelled(auction_id) { 0 } else { self.get_units(auction_id) };
+            let mut steps = 0;
+            while settlement.phase != SettlementPhase::Done && steps < max_steps {
+                let index = match settlement.cursor {
+                    Some(index) if settlement.phase == SettlementPhase::Pricing && settlement.units_filled < units => index,
+                    Some(index) if settlement.phase == SettlementPhase::Paying => index,
+                    _ => {
+                        self.finish_settlement_phase(auction_id, &mut settlement);
+                        continue
+                    }
+                };
+                let bid = self.get_unit_bid(auction_id, index).expect("listed bids exist");
+                steps += 1;
+                settlement.cursor = bid.next;
+
+                if settlement.phase == SettlementPhase::Pricing {
+                    settlement.units_filled += core::cmp::min(bid.quantity, units - settlement.units_filled);
+                    settlement.clearing_price = bid.unit_price;
+                    continue
+                }
+
+                let filled = core::cmp::min(bid.quantity, settlement.units_sold - settlement.units_filled);
+                settlement.units_filled += filled;
+                let paid = settlement.clearing_price * Balance::from(filled);
+                self.add_pending_returns(bid.bidder, bid.unit_price * Balance::from(bid.quantity) - paid);
+                if filled > 0 {
+                    let won = *self.units_won.get(&(auction_id, bid.bidder)).unwrap_or(&0);
+                    self.units_won.insert((auction_id, bid.bidder), won + filled);
+                    self.env().emit_event(Units_Won {
+                        auction_id,
+                        bidder: Some(bid.bidder),
+                        quantity: filled,
+                        unit_price: settlement.clearing_price,
+                    });
+                }
+            }
+
+            self.unit_settlement.insert(auction_id, settlement);
+            settlement.phase == SettlementPhase::Done
+        }
+
+        /// moves a multi-unit lot's settlement on to its next phase
+        fn finish_settlement_phase(&mut self, auction_id: AuctionId, settlement: &mut UnitSettlement) {
+            if settlement.phase == SettlementPhase::Pricing {
+                settlement.phase = SettlementPhase::Paying;
+                settlement.units_sold = settlement.units_filled;
+                settlement.units_filled = 0;
+                settlement.cursor = self.unit_bid_head.get(&auction_id).cloned();
+                if settlement.units_sold == 0 {
+                    settlement.clearing_price = 0;
+                }
+                return
+            }
+
+            settlement.phase = SettlementPhase::Done;
+            self.distribute_proceeds(auction_id, settlement.clearing_price * Balance::from(settlement.units_sold));
+            self.env().emit_event(Units_Settled {
+                auction_id,
+                units_sold: settlement.units_sold,
+                clearing_price: settlement.clearing_price,
+            });
+        }
+
+        /// returns the number of units for sale on the multi-unit lot
+        #[ink(message)]
+        fn get_units(&self, auction_id: AuctionId) -> u32 {
+            *self.units.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the number of bids placed on the multi-unit lot
+        #[ink(message)]
+        fn get_unit_bid_count(&self, auction_id: AuctionId) -> u32 {
+            *self.unit_bid_count.get(&auction_id).unwrap_or(&0)
+        }
+
+        /// returns the bid with the given index on the multi-unit lot
+        #[ink(message)]
+        fn get_unit_bid(&self, auction_id: AuctionId, index: u32) -> Option<UnitBid> {
+            self.unit_bids.get(&(auction_id, index)).cloned()
+        }
+
+        /// returns how far the settlement of the multi-unit lot has got
+        #[ink(message)]
+        fn get_unit_settlement(&self, auction_id: AuctionId) -> UnitSettlement {
+            self.unit_settlement.get(&auction_id).cloned().unwrap_or(UnitSettlement {
+                phase: SettlementPhase::Pricing,
+                cursor: self.unit_bid_head.get(&auction_id).cloned(),
+                units_filled: 0,
+                units_sold: 0,
+                clearing_price: 0,
+            })
+        }
+
+        /// returns the number of units the account won on the multi-unit lot, final once it is settled
+        #[ink(message)]
+        fn get_units_won(&self, auction_id: AuctionId, id: AccountId) -> u32 {
+            *self.units_won.get(&(auction_id, id)).unwrap_or(&0)
+        }
+
+        /// Once a candle lot is ended anyone can draw its close in the block after the one it was ended in, which
+        /// pays the lot out to the leader as of the close like `end` does for other lots. Every call in that block
+        /// draws the same close, so a bidder who doesn't like it can only leave the call to someone else. If nobody
+        /// draws in that block, a later call moves the draw to the block after its own and returns false
+        #[ink(message)]
+        fn draw_candle(&mut self, auction_id: AuctionId) -> bool {
+            let end_block = match self.get_candle_end_block(auction_id) {
+                Some(end_block) => end_block,
+                None => return false,
+            };
+            let block = self.env().block_number();
+            if self.get_candle_close(auction_id).is_some() || block <= end_block {
+                self.env().emit_event(Wrong_Phase {
+                    auction_id,
+                    sender: Some(self.env().caller()),
+                    time: self.get_time(),
                 });
                 return false
             }
-            // if the bid is not higher than the current highest, then return false
-            else if amount <= previous_highest_bid {
-                //value is to low so allow the sender to collect the funds
-                self.pending_returns.insert(sender, amount + sender_curr_pending);
+            else if block > end_block + 1 {
+                // the randomness of the missed block is known by now, so the draw has to wait for the next one
+                self.candle_end_block.insert(auction_id, block);
+                self.env().emit_event(Candle_Ended {
+                    auction_id,
+                    end_block: block,
+                });
+                return false
+            }
+
+            self.close_candle(auction_id, end_block);
+            self.env().emit_event(Ended {
+                auction_id,
+                highest_bidder: Some(self.get_highest_bidder(auction_id)),
+                highest_bid: self.get_highest_bid(auction_id),
+            });
+            self.pay_out(auction_id);
+            self.deliver_asset_of(auction_id);
+            true
+        }
+
+        /// draws the close of an ended candle lot and makes the leader as of the close its highest bidder, the lot
+        /// goes unsold if nobody had bid by then
+        fn close_candle(&mut self, auction_id: AuctionId, end_block: BlockNumber) {
+            let slots = self.get_candle_slots(auction_id);
+            let close = if slots == 0 {
+                0
+            } else {
+                let seed = scale::Encode::using_encoded(&(auction_id, end_block), |subject| self.env().random(subject));
+                let bytes = seed.as_ref();
+                u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) % slots + 1
+            };
+            self.candle_close.insert(auction_id, close);
+
+            // the leader as of the close is the last one recorded in a slot up to it
+            let mut winner = None;
+            for slot in (0..=close).rev() {
+                if let Some(leader) = self.get_candle_leader(auction_id, slot) {
+                    winner = Some((leader, *self.candle_bids.get(&(auction_id, slot)).unwrap_or(&0)));
+                    break
+                }
+            }
+            let (winner, winning_bid) = winner.unwrap_or((self.get_beneficiary(auction_id), 0));
+            // the winning bid leaves the winner's lock and is paid out like the highest bid of any lot
+            let locked = self.get_candle_locked(auction_id, winner);
+            self.candle_locked.insert((auction_id, winner), locked.saturating_sub(winning_bid));
+            self.second_highest_bid.insert(auction_id, 0);
+            self.highest_bid.insert(auction_id, winning_bid);
+            self.highest_bidder.insert(auction_id, winner);
+
+            self.env().emit_event(Candle_Closed {
+                auction_id,
+                close_slot: close,
+                winner: Some(winner),
+                winning_bid,
+            });
+        }
 
-                // emit event
-                self.env().emit_event(Failed_Bid_Lower_Than_Highest_Bid {
-                    attempted_bidder: Some(sender),
-                    attempted_bid: amount,
-                    highest_bidder: Some(self.get_highest_bidder()),
-                    highest_bid: self.get_highest_bid(),
-                });
+        /// Once the close of a candle lot is drawn, or the lot is cancelled, anyone can return a bidder's locked bid,
+        /// less what they paid if they won, to the bidder's withdraw balance
+        #[ink(message)]
+        fn release_candle_bid(&mut self, auction_id: AuctionId, bidder: AccountId) -> bool {
+            let amount = self.get_candle_locked(auction_id, bidder);
+            if !(self.is_cancelled(auction_id) || self.get_candle_close(auction_id).is_some()) || amount == 0 {
                 return false
             }
 
+            self.candle_locked.remove(&(auction_id, bidder));
+            self.add_pending_returns(bidder, amount);
+            self.env().emit_event(Candle_Bid_Released {
+                auction_id,
+                bidder: Some(bidder),
+                amount,
+            });
+            true
+        }
 
-            // Sending back the money by simply using a command is a security risk
-            // because it could execute an untrusted contract.
-            // It is safer to let the recipients withdraw their money themselves.
-            // look up "reentracy attack"
-            let previous_highest_bidder = self.get_highest_bidder();
-            let curr_pending = self.curr_withdrawl_amount(previous_highest_bidder);
-            // the curr_withdrawl_amount is here in case the highest bidder had other pending money
-            self.pending_returns.insert(previous_highest_bidder, 
-                                        previous_highest_bid + curr_pending);
+        /// returns the number of slots in the candle lot's ending period
+        #[ink(message)]
+        fn get_candle_slots(&self, auction_id: AuctionId) -> u32 {
+            *self.candle_slots.get(&auction_id).unwrap_or(&0)
+        }
 
+        /// returns the length of a slot of the candle lot in milliseconds (or blocks)
+        #[ink(message)]
+        fn get_candle_slot_length(&self, auction_id: AuctionId) -> Timestamp {
+            *self.candle_slot_length.get(&auction_id).unwrap_or(&0)
+        }
 
-            // change the highest bidder to the new highest bidder
-            self.highest_bid.set(amount);
-            self.highest_bidder.set(self.env().caller());
+        /// returns the candle lot's current slot, 0 before its ending period and 1 to `get_candle_slots` during it
+        #[ink(message)]
+        fn get_candle_slot(&self, auction_id: AuctionId) -> u32 {
+            let slots = self.get_candle_slots(auction_id);
+            let slot_length = self.get_candle_slot_length(auction_id);
+            let ending_start = self.get_end_time(auction_id).saturating_sub(u64::from(slots) * slot_length);
+            if slots == 0 || self.get_time() < ending_start {
+                return 0
+            }
+            core::cmp::min((self.get_time() - ending_start) / slot_length + 1, u64::from(slots)) as u32
+        }
 
-            // emit event
-            self.env().emit_event(New_Highest_Bid {
-                previous_highest_bidder: Some(previous_highest_bidder),
-                previous_highest_bid: previous_highest_bid,
-                highest_bidder: Some(self.env().caller()),
-                highest_bid: amount,
-            });
+        /// returns who led the candle lot at the end of the given slot, if a bid was made during it
+        #[ink(message)]
+        fn get_candle_leader(&self, auction_id: AuctionId, slot: u32) -> Option<AccountId> {
+            self.candle_leaders.get(&(auction_id, slot)).cloned()
+        }
 
-            true
+        /// returns the slot drawn as the close of the candle lot, once it is settled
+        #[ink(message)]
+        fn get_candle_close(&self, auction_id: AuctionId) -> Option<u32> {
+            self.candle_close.get(&auction_id).cloned()
         }
 
+        /// returns the block before the one the candle lot's close is drawn in, the block it was ended in unless
+        /// nobody drew in time
+        #[ink(message)]
+        fn get_candle_end_block(&self, auction_id: AuctionId) -> Option<BlockNumber> {
+            self.candle_end_block.get(&auction_id).cloned()
+        }
+
+        /// returns the amount of the account's bids still locked by the candle lot
+        #[ink(message)]
+        fn get_candle_locked(&self, auction_id: AuctionId, id: AccountId) -> Balance {
+            *self.candle_locked.get(&(auction_id, id)).unwrap_or(&0)
+        }
 
-        /// this function transfers all the sender's pending withdraw balance to the sender
+        /// this function transfers all the sender's pending withdraw balance to the sender, in the payment token
+        /// if the house has one
         #[ink(message)]
-        fn withdraw(&mut self) -> bool {
+        fn withdraw(&mut self) -> Result<(), AuctionError> {
             let sender = self.env().caller();
             let amount = self.curr_withdrawl_amount(sender);
-            
+
             if amount == 0 {
-                return false
+                return Err(AuctionError::NothingToWithdraw)
             }
             //remove the balance
             self.pending_returns.insert(sender, 0);
-            
+
             //return amount to owner
-            match self.env().transfer(sender, amount) {
-                Ok(now) => (),
-                Err(error) => {
+            if let Some(token) = self.get_payment_token() {
+                if !self.transfer_asset(Asset::Fungible { contract: token, amount }, self.env().account_id(), sender) {
                     //Since the amount is not returned re-add the amount to pending_returns
                     self.pending_returns.insert(sender, amount);
-                    return false
+                    self.env().emit_event(Token_Transfer_Failed {
+                        sender: Some(sender),
+                        amount,
+                    });
+                    return Err(AuctionError::TokenTransferFailed)
                 }
-            };
+            }
+            else {
+                match self.env().transfer(sender, amount) {
+                    Ok(now) => (),
+                    Err(error) => {
+                        //Since the amount is not returned re-add the amount to pending_returns
+                        self.pending_returns.insert(sender, amount);
+                        return Err(AuctionError::TransferFailed)
+                    }
+                };
+            }
 
             self.env().emit_event(Withdrawal {
                 account: Some(sender),
                 amount: amount,
             });
-            true
+            Ok(())
         }
 
     }
@@ -396,17 +4204,76 @@ mod auction {
     mod tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
+        use std::cell::RefCell;
         use std::convert::TryFrom;
+        use ink_core::env;
+
+        thread_local! {
+            // encoded answers to the next cross-contract calls, None for a call that fails, oldest first
+            static ANSWERS: RefCell<Vec<Option<Vec<u8>>>> = RefCell::new(Vec::new());
+        }
+
+        // queues the encoded answers to the next cross-contract calls
+        fn mock_answers(answers: &[Option<Vec<u8>>]) {
+            ANSWERS.with(|queue| queue.borrow_mut().extend_from_slice(answers));
+        }
+
+        // queues what the next cross-contract transfers return, encoded as the called contract would
+        fn mock_transfers(results: &[TransferResult]) {
+            let answers: Vec<_> = results.iter().map(|result| Some(scale::Encode::encode(result))).collect();
+            mock_answers(&answers);
+        }
+
+        // decodes the answer to the next cross-contract call like `CallParams::fire` does
+        pub(super) fn next_answer<R: scale::Decode>() -> Option<R> {
+            let answer = ANSWERS.with(|queue| {
+                let mut queue = queue.borrow_mut();
+                assert!(!queue.is_empty(), "unexpected cross-contract call");
+                queue.remove(0)
+            });
+            answer.and_then(|encoded| scale::Decode::decode(&mut &encoded[..]).ok())
+        }
+
+        // makes the following calls come from `sender` with `value` transferred to the contract
+        fn set_sender_and_value(sender: AccountId, value: Balance) {
+            let callee = env::account_id::<EnvTypes>().unwrap_or([0x0; 32].into());
+            env::test::push_execution_context::<EnvTypes>(
+                sender,
+                callee,
+                1000000,
+                value,
+                env::test::CallData::new(env::call::Selector::from_str("")),
+            );
+        }
+
+        // like set_sender_and_value but also credits `value` to the contract's balance, as a real transfer would
+        fn transfer_in(sender: AccountId, value: Balance) {
+            set_sender_and_value(sender, value);
+            let callee = env::account_id::<EnvTypes>().expect("Cannot get contract account");
+            let balance = env::test::get_account_balance::<EnvTypes>(callee).unwrap_or(0);
+            env::test::set_account_balance::<EnvTypes>(callee, balance + value).expect("Cannot set contract balance");
+        }
+
+        // everything the contract owes: the withdraw balances of the given accounts and the bids locked in open lots
+        fn liabilities(auction: &Auction, accounts: &[AccountId]) -> Balance {
+            let pending: Balance = accounts.iter().map(|id| auction.curr_withdrawl_amount(*id)).sum();
+            let locked: Balance = (0..auction.get_auction_count())
+                .filter(|auction_id| !auction.is_ended(*auction_id))
+                .map(|auction_id| auction.get_locked_amount(auction_id))
+                .sum();
+            pending + locked
+        }
 
 
         // We test if the default constructor does its job.
         #[test]
         fn default_works() {
             let auction = Auction::default();
-            assert_eq!(auction.highest_bid, 0);
-            assert_eq!(auction.ended , false);
+            assert_eq!(auction.get_auction_count(), 1);
+            assert_eq!(auction.get_highest_bid(0), 0);
+            assert_eq!(auction.is_ended(0), false);
             assert_eq!(auction.pending_returns.len(), 1);
-            assert_eq!{auction.get_current_asking_price(), 0};
+            assert_eq!{auction.get_current_asking_price(0), 1};
         }
 
         // We test if the ne constructor does its job.
@@ -414,24 +4281,1040 @@ mod auction {
         fn new_works() {
             // a positive starting price begins as the highest bid
             let auction = Auction::new(5, 60);
-            assert_eq!(auction.get_starting_price(), 5);
+            assert_eq!(auction.get_starting_price(0), 5);
         }
 
         // we test that getters work
         #[test]
         fn getters_works() {
             let auction = Auction::default();
-            assert_eq!(auction.highest_bid, auction.get_current_asking_price());
-            assert_eq!(auction.highest_bidder, auction.get_highest_bidder());
+            assert_eq!(auction.get_starting_price(0) + 1, auction.get_current_asking_price(0));
+            assert_eq!(auction.get_min_increment(), Increment::Absolute(1));
+            assert_eq!(*auction.highest_bidder.get(&0).unwrap(), auction.get_highest_bidder(0));
         }
 
         // we test that owner can end ballot
         #[test]
         fn end_works() {
             let mut auction = Auction::default();
-            assert!(auction.end());
-            assert!(auction.is_ended());
-            assert!(!auction.end());
+            assert!(auction.end(0).is_ok());
+            assert!(auction.is_ended(0));
+            assert!(auction.end(0).is_err());
+        }
+
+        // we test that lots are created with their own ids and ended independently
+        #[test]
+        fn house_lots_are_independent() {
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            assert_eq!(auction.get_auction_count(), 0);
+            assert_eq!(auction.create_auction(5, 60), 0);
+            assert_eq!(auction.create_auction(10, 120), 1);
+            assert_eq!(auction.get_auction_count(), 2);
+            assert_eq!(auction.get_starting_price(1), 10);
+            assert!(auction.end(1).is_ok());
+            assert!(auction.is_ended(1));
+            assert!(!auction.is_ended(0));
+        }
+
+        // we test that unknown lots can't be ended
+        #[test]
+        fn end_unknown_lot_fails() {
+            let mut auction = Auction::default();
+            assert!(auction.end(7).is_err());
+            assert!(!auction.is_ended(7));
+        }
+
+        // we test that a sealed lot only takes commitments and that the highest reveal leads
+        #[test]
+        fn sealed_commit_reveal_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_sealed_auction(1, 0, 1_000_000, 5_000, PriceRule::FirstPrice);
+            let salt = Hash::from([0x1; 32]);
+            let commitment = auction.compute_commitment(40, salt);
+
+            set_sender_and_value(accounts.bob, 50);
+            assert!(auction.bid(auction_id).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 50);
+            assert!(auction.commit_bid(auction_id, commitment).is_ok());
+            assert_eq!(auction.get_deposit(auction_id, accounts.bob), 50);
+            // the beneficiary can't change the rules once someone committed
+            set_sender_and_value(accounts.alice, 0);
+            assert!(!auction.set_eligibility(auction_id, Eligibility::Allowlist));
+            set_sender_and_value(accounts.charlie, 30);
+            assert!(auction.commit_bid(auction_id, auction.compute_commitment(80, salt)).is_ok());
+            assert_eq!(auction.get_commitment_count(auction_id), 2);
+            set_sender_and_value(accounts.bob, 0);
+
+            // reveals are only accepted once the commit phase is over
+            assert!(!auction.reveal_bid(auction_id, 40, salt));
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(!auction.reveal_bid(auction_id, 41, salt));
+            assert!(auction.reveal_bid(auction_id, 40, salt));
+            assert_eq!(auction.get_highest_bid(auction_id), 40);
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.bob);
+            // the unused part of the deposit can be withdrawn
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 60);
+            assert_eq!(auction.get_commitment(auction_id, accounts.bob), None);
+
+            // a reveal the deposit doesn't cover is recorded as such and the deposit returned
+            set_sender_and_value(accounts.charlie, 0);
+            assert!(auction.reveal_bid(auction_id, 80, salt));
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.bob);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 30);
+            let history = auction.get_account_bids(auction_id, accounts.charlie, 0, 10);
+            assert_eq!(history.last().map(|record| record.status), Some(BidStatus::ExceedsDeposit));
+
+            // nobody can end the lot before the reveal phase is over
+            assert!(auction.end(auction_id).is_err());
+        }
+
+        // we test that a second-price lot clears at the runner-up's bid
+        #[test]
+        fn second_price_clearing_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_sealed_auction(10, 0, 1_000_000, 0, PriceRule::SecondPrice);
+            let salt = Hash::from([0x2; 32]);
+
+            set_sender_and_value(accounts.bob, 40);
+            assert!(auction.commit_bid(auction_id, auction.compute_commitment(40, salt)).is_ok());
+            set_sender_and_value(accounts.charlie, 30);
+            assert!(auction.commit_bid(auction_id, auction.compute_commitment(30, salt)).is_ok());
+
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.reveal_bid(auction_id, 40, salt));
+            // without a runner-up the starting price is paid
+            assert_eq!(auction.get_clearing_price(auction_id), 10);
+            set_sender_and_value(accounts.charlie, 0);
+            assert!(auction.reveal_bid(auction_id, 30, salt));
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.bob);
+            assert_eq!(auction.get_second_highest_bid(auction_id), 30);
+            assert_eq!(auction.get_clearing_price(auction_id), 30);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 30);
+        }
+
+        // we test that the first bid covering a dutch lot's asking price wins it at that price
+        #[test]
+        fn dutch_buy_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_dutch_auction(100, 20, 1_000_000, PriceDecay::Step(500_000));
+            assert_eq!(auction.get_current_asking_price(auction_id), 100);
+
+            set_sender_and_value(accounts.bob, 90);
+            assert!(auction.bid(auction_id).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 90);
+
+            set_sender_and_value(accounts.charlie, 120);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(auction.is_ended(auction_id));
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.charlie);
+            assert_eq!(auction.get_highest_bid(auction_id), 100);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 20);
+            assert_eq!(auction.curr_withdrawl_amount(auction.get_beneficiary(auction_id)), 100);
+        }
+
+        // we test that a dutch lot without a window sits at its floor price
+        #[test]
+        fn dutch_price_decays_to_floor() {
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_dutch_auction(100, 20, 0, PriceDecay::Linear);
+            assert_eq!(auction.get_current_asking_price(auction_id), 20);
+            let auction_id = auction.create_dutch_auction(100, 200, 0, PriceDecay::Linear);
+            assert_eq!(auction.get_floor_price(auction_id), 100);
+        }
+
+        // we test the linear and step asking prices partway through the decay window and at the floor
+        #[test]
+        fn dutch_price_decays_over_time() {
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            // windows are given in blocks' worth of time so every block moves the price by a known amount
+            let before = auction.get_time();
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            let block_time = auction.get_time() - before;
+            assert!(block_time > 0);
+
+            let linear = auction.create_dutch_auction(100, 20, 4 * block_time, PriceDecay::Linear);
+            let step = auction.create_dutch_auction(100, 20, 4 * block_time, PriceDecay::Step(2 * block_time));
+            assert_eq!(auction.get_current_asking_price(linear), 100);
+            assert_eq!(auction.get_current_asking_price(step), 100);
+
+            let expected = [(80, 100), (60, 60), (40, 60), (20, 20), (20, 20)];
+            for (linear_price, step_price) in expected.iter() {
+                env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+                assert_eq!(auction.get_current_asking_price(linear), *linear_price);
+                assert_eq!(auction.get_current_asking_price(step), *step_price);
+            }
+        }
+
+        // we test that bids in the extension window push end_time out up to the cap
+        #[test]
+        fn anti_sniping_extends_end_time() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 1000);
+            let end_time = auction.get_end_time(auction_id);
+            assert!(auction.set_anti_sniping(auction_id, 2000, 300, 500));
+
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.get_end_time(auction_id), end_time + 300);
+            set_sender_and_value(accounts.charlie, 20);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.get_end_time(auction_id), end_time + 500);
+            assert_eq!(auction.get_total_extension(auction_id), 500);
+            assert_eq!(auction.get_time_left(auction_id), end_time + 500 - auction.get_time());
+
+            // settings are locked once the lot has been bid on
+            set_sender_and_value(accounts.alice, 0);
+            assert!(!auction.set_anti_sniping(auction_id, 0, 0, 0));
+        }
+
+        // we test that a new bid has to beat the highest bid by the minimum increment
+        #[test]
+        fn min_increment_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::BasisPoints(1_000), None);
+            let auction_id = auction.create_auction(0, 60);
+
+            set_sender_and_value(accounts.bob, 100);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.get_current_asking_price(auction_id), 110);
+
+            set_sender_and_value(accounts.charlie, 105);
+            assert!(auction.bid(auction_id).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 105);
+            set_sender_and_value(accounts.charlie, 110);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.charlie);
+        }
+
+        // we test that a bid covering the buy-now price settles the lot instantly
+        #[test]
+        fn buy_now_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(!auction.set_buy_now_price(auction_id, 0, 5_000));
+            assert_eq!(auction.get_buy_now_price(auction_id), None);
+            assert!(auction.set_buy_now_price(auction_id, 100, 5_000));
+
+            set_sender_and_value(accounts.bob, 50);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.get_buy_now_price(auction_id), Some(100));
+
+            set_sender_and_value(accounts.charlie, 120);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(auction.is_ended(auction_id));
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.charlie);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 20);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 50);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 100);
+        }
+
+        // we test that buying now is withdrawn once the highest bid passes the threshold
+        #[test]
+        fn buy_now_is_withdrawn() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_buy_now_price(auction_id, 100, 5_000));
+
+            set_sender_and_value(accounts.bob, 60);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.get_buy_now_price(auction_id), None);
+            set_sender_and_value(accounts.charlie, 100);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(!auction.is_ended(auction_id));
+        }
+
+        // we test that a proxy bids in increments up to its maximum and releases the rest
+        #[test]
+        fn proxy_bidding_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(5), None);
+            let auction_id = auction.create_auction(0, 60);
+
+            set_sender_and_value(accounts.bob, 100);
+            assert!(auction.bid_proxy(auction_id).is_ok());
+            assert_eq!(auction.get_highest_bid(auction_id), 1);
+
+            // a manual bid below the maximum is outbid straight away
+            set_sender_and_value(accounts.charlie, 50);
+            assert!(auction.bid(auction_id).is_err());
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.bob);
+            assert_eq!(auction.get_highest_bid(auction_id), 55);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 50);
+
+            // a bigger proxy takes over and the whole maximum of the previous one is released
+            set_sender_and_value(accounts.charlie, 200);
+            assert!(auction.bid_proxy(auction_id).is_ok());
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.charlie);
+            assert_eq!(auction.get_highest_bid(auction_id), 105);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 100);
+
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 105);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 50 + 95);
+        }
+
+        // we test that outbid bidders can top up from their withdraw balance and that
+        // the contract never owes more than it holds
+        #[test]
+        fn cumulative_bids_stay_covered() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let all = [accounts.alice, accounts.bob, accounts.charlie];
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            let initial_balance = auction.get_contract_balance();
+
+            transfer_in(accounts.bob, 50);
+            assert!(auction.bid(auction_id).is_ok());
+            transfer_in(accounts.charlie, 60);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(liabilities(&auction, &all) <= auction.get_contract_balance() - initial_balance);
+
+            // bob tops his returned 50 up by 20
+            transfer_in(accounts.bob, 20);
+            assert!(auction.bid_with_pending(auction_id, 50).is_ok());
+            assert_eq!(auction.get_highest_bid(auction_id), 70);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+            assert!(liabilities(&auction, &all) <= auction.get_contract_balance() - initial_balance);
+
+            // charlie can't bid more than their withdraw balance
+            transfer_in(accounts.charlie, 0);
+            assert!(auction.bid_from_pending(auction_id, 100).is_err());
+            assert!(auction.bid_from_pending(auction_id, 60).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 60);
+            assert!(liabilities(&auction, &all) <= auction.get_contract_balance() - initial_balance);
+
+            transfer_in(accounts.charlie, 15);
+            assert!(auction.bid_with_pending(auction_id, 60).is_ok());
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.charlie);
+            assert_eq!(liabilities(&auction, &all), auction.get_contract_balance() - initial_balance);
+
+            transfer_in(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert_eq!(liabilities(&auction, &all), auction.get_contract_balance() - initial_balance);
+        }
+
+        // we test that accepted and rejected bids are logged and can be paged through
+        #[test]
+        fn bid_history_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(10, 60);
+
+            set_sender_and_value(accounts.bob, 20);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.charlie, 15);
+            assert!(auction.bid(auction_id).is_err());
+            set_sender_and_value(accounts.charlie, 30);
+            assert!(auction.bid(auction_id).is_ok());
+
+            assert_eq!(auction.get_bid_count(auction_id), 3);
+            let bids = auction.get_bids(auction_id, 1, 10);
+            assert_eq!(bids.len(), 2);
+            assert_eq!(bids[0].bidder, accounts.charlie);
+            assert_eq!(bids[0].amount, 15);
+            assert_eq!(bids[0].status, BidStatus::LowerThanHighestBid);
+            assert_eq!(bids[1].status, BidStatus::Accepted);
+
+            assert_eq!(auction.get_account_bid_count(auction_id, accounts.charlie), 2);
+            let charlie_bids = auction.get_account_bids(auction_id, accounts.charlie, 1, 1);
+            assert_eq!(charlie_bids.len(), 1);
+            assert_eq!(charlie_bids[0].amount, 30);
+            assert_eq!(auction.get_bids(auction_id, 3, 10).len(), 0);
+        }
+
+        // we test that bids before a scheduled start are returned
+        #[test]
+        fn scheduled_start_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let start_time = auction.get_time() + 1000;
+            let auction_id = auction.create_scheduled_auction(0, start_time, 60);
+            assert_eq!(auction.get_start_time(auction_id), start_time);
+            assert_eq!(auction.get_time_until_start(auction_id), 1000);
+            assert_eq!(auction.get_end_time(auction_id), start_time + 60);
+            assert!(!auction.has_started(auction_id));
+
+            set_sender_and_value(accounts.bob, 20);
+            assert!(auction.bid(auction_id).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 20);
+            assert!(auction.end(auction_id).is_err());
+
+            // lots without a schedule open right away
+            let auction_id = auction.create_auction(0, 60);
+            assert_eq!(auction.get_time_until_start(auction_id), 0);
+            assert!(auction.has_started(auction_id));
+        }
+
+        // we test that a token house exposes its token and refuses native bids
+        #[test]
+        fn token_house_refuses_native_bids() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_with_token(accounts.django, 0, 60);
+            assert_eq!(auction.get_payment_token(), Some(accounts.django));
+            assert_eq!(Auction::default().get_payment_token(), None);
+
+            // the native balance is sent back instead of being credited in the token
+            transfer_in(accounts.bob, 10);
+            assert!(auction.bid(0).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+            assert_eq!(auction.get_highest_bid(0), 0);
+
+            // and a native house refuses token bids for the same reason
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert_eq!(auction.bid_tokens(auction_id, 10), Err(AuctionError::WrongCurrency));
+            assert_eq!(auction.register_bond_tokens(auction_id, 10), Err(AuctionError::WrongCurrency));
+        }
+
+        // we test that token bids are only placed once the tokens are pulled and that withdrawals are paid in the token
+        #[test]
+        fn token_bids_and_withdrawals_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_with_token(accounts.django, 0, 60);
+
+            set_sender_and_value(accounts.bob, 0);
+            mock_transfers(&[Ok(())]);
+            assert!(auction.bid_tokens(0, 10).is_ok());
+            assert_eq!(auction.get_highest_bid(0), 10);
+
+            // charlie has not approved enough tokens, the pull fails and nothing is credited
+            set_sender_and_value(accounts.charlie, 0);
+            mock_transfers(&[Err(1)]);
+            assert_eq!(auction.bid_tokens(0, 20), Err(AuctionError::TokenTransferFailed));
+            assert_eq!(auction.get_highest_bid(0), 10);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 0);
+
+            mock_transfers(&[Ok(())]);
+            assert!(auction.bid_tokens(0, 20).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 10);
+
+            // a failed token transfer leaves the withdraw balance in place, a successful one empties it
+            set_sender_and_value(accounts.bob, 0);
+            mock_transfers(&[Err(0)]);
+            assert_eq!(auction.withdraw(), Err(AuctionError::TokenTransferFailed));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 10);
+            mock_transfers(&[Ok(())]);
+            assert!(auction.withdraw().is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+            assert_eq!(auction.withdraw(), Err(AuctionError::NothingToWithdraw));
+        }
+
+        // we test that lots without an escrowed asset have nothing to deliver
+        #[test]
+        fn lots_without_asset_deliver_nothing() {
+            let mut auction = Auction::default();
+            assert_eq!(auction.get_asset(0), None);
+            assert!(!auction.deliver_asset(0));
+            assert!(auction.end(0).is_ok());
+            assert!(!auction.deliver_asset(0));
+            assert!(!auction.is_asset_delivered(0));
+        }
+
+        // we test that the asset is escrowed on creation and that a failed delivery can be retried
+        #[test]
+        fn asset_escrow_and_delivery_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let asset = Asset::Nft { contract: accounts.frank, token_id: 7 };
+
+            // the nft contract refuses to hand the token over, can't be reached or answers something else
+            mock_transfers(&[Err(0)]);
+            assert_eq!(auction.create_asset_auction(0, 60, asset), None);
+            mock_answers(&[None, Some(vec![2])]);
+            assert_eq!(auction.create_asset_auction(0, 60, asset), None);
+            assert_eq!(auction.create_asset_auction(0, 60, asset), None);
+            assert_eq!(auction.get_auction_count(), 0);
+
+            mock_transfers(&[Ok(())]);
+            assert_eq!(auction.create_asset_auction(0, 60, asset), Some(0));
+            assert_eq!(auction.get_asset(0), Some(asset));
+            assert!(!auction.is_asset_delivered(0));
+            assert!(!auction.deliver_asset(0));
+
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(0).is_ok());
+            assert_eq!(auction.get_asset_recipient(0), accounts.bob);
+
+            // the delivery on end fails, the lot is still ended and the asset stays escrowed
+            set_sender_and_value(accounts.alice, 0);
+            mock_transfers(&[Err(1)]);
+            assert!(auction.end(0).is_ok());
+            assert!(!auction.is_asset_delivered(0));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 10);
+
+            // anyone can retry it, and once delivered it can't be delivered again
+            set_sender_and_value(accounts.charlie, 0);
+            mock_transfers(&[Ok(())]);
+            assert!(auction.deliver_asset(0));
+            assert!(auction.is_asset_delivered(0));
+            assert!(!auction.deliver_asset(0));
+        }
+
+        // we test that the highest bid is returned when it does not meet the reserve
+        #[test]
+        fn reserve_not_met_refunds_bidder() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_reserve_price(auction_id, 50));
+            assert!(auction.has_reserve_price(auction_id));
+
+            set_sender_and_value(accounts.bob, 40);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(!auction.is_reserve_met(auction_id));
+
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 40);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 0);
+        }
+
+        // we test that failures report their reason and that a reverting house sends rejected bids back
+        #[test]
+        fn typed_errors_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(10, 60);
+
+            set_sender_and_value(accounts.bob, 5);
+            assert_eq!(auction.bid(auction_id), Err(AuctionError::LowerThanStartingPrice));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 5);
+            assert_eq!(auction.bid(auction_id + 1), Err(AuctionError::NoSuchAuction));
+            assert_eq!(auction.end(auction_id), Err(AuctionError::EndTooEarly));
+            set_sender_and_value(accounts.charlie, 0);
+            assert_eq!(auction.withdraw(), Err(AuctionError::NothingToWithdraw));
+            set_sender_and_value(accounts.alice, 0);
+            assert_eq!(auction.end(auction_id), Ok(()));
+            assert_eq!(auction.end(auction_id), Err(AuctionError::AlreadyEnded));
+
+            let mut auction = Auction::new_marketplace(Increment::Absolute(1), None, None, Vec::new(),
+                                                       RejectionPolicy::Revert);
+            assert_eq!(auction.get_rejection_policy(), RejectionPolicy::Revert);
+            let auction_id = auction.create_auction(10, 60);
+            let initial_balance = auction.get_contract_balance();
+            transfer_in(accounts.bob, 5);
+            assert_eq!(auction.bid(auction_id), Err(AuctionError::LowerThanStartingPrice));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+            assert_eq!(auction.get_contract_balance(), initial_balance);
+
+            // the other payable messages send rejected money back as well
+            transfer_in(accounts.bob, 5);
+            assert_eq!(auction.bid_proxy(auction_id), Err(AuctionError::BelowMinIncrement));
+            transfer_in(accounts.bob, 5);
+            assert_eq!(auction.commit_bid(auction_id, Hash::from([0; 32])), Err(AuctionError::WrongAuctionKind));
+            transfer_in(accounts.bob, 5);
+            assert_eq!(auction.bid_units(auction_id, 1, None), Err(AuctionError::WrongAuctionKind));
+            transfer_in(accounts.bob, 5);
+            assert_eq!(auction.register_bond(auction_id), Err(AuctionError::NoBondRequired));
+            transfer_in(accounts.bob, 5);
+            assert_eq!(auction.confirm_purchase(auction_id), Err(AuctionError::NotAwaitingConfirmation));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+            assert_eq!(auction.get_contract_balance(), initial_balance);
+        }
+
+        // we test that bonded lots need a bond to bid on and a confirmation from the winner
+        #[test]
+        fn bid_bonds_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_bid_bond(auction_id, 5, 0));
+
+            set_sender_and_value(accounts.bob, 10);
+            assert_eq!(auction.bid(auction_id), Err(AuctionError::BondRequired));
+            set_sender_and_value(accounts.bob, 5);
+            assert!(auction.register_bond(auction_id).is_ok());
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.charlie, 5);
+            assert!(auction.register_bond(auction_id).is_ok());
+            set_sender_and_value(accounts.charlie, 20);
+            assert!(auction.bid(auction_id).is_ok());
+
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert!(auction.is_awaiting_confirmation(auction_id));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 0);
+            assert!(!auction.release_bond(auction_id, accounts.charlie));
+            assert!(!auction.release_bond(auction_id, accounts.bob));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 10);
+
+            // charlie lets the deadline pass, the lot is offered to bob
+            assert!(!auction.forfeit_bond(auction_id));
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(auction.forfeit_bond(auction_id));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 5);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 20);
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.bob);
+
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.confirm_purchase(auction_id).is_ok());
+            assert!(!auction.is_awaiting_confirmation(auction_id));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 5);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 15);
+            assert_eq!(auction.get_bond(auction_id, accounts.bob), 0);
+        }
+
+        // we test that a bonded lot missing its reserve is settled right away instead of awaiting confirmation
+        #[test]
+        fn bonded_lot_below_reserve_settles() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_bid_bond(auction_id, 5, 0));
+            assert!(auction.set_reserve_price(auction_id, 50));
+
+            set_sender_and_value(accounts.bob, 5);
+            assert!(auction.register_bond(auction_id).is_ok());
+            set_sender_and_value(accounts.bob, 40);
+            assert!(auction.bid(auction_id).is_ok());
+
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert!(!auction.is_awaiting_confirmation(auction_id));
+            assert!(!auction.forfeit_bond(auction_id));
+            assert!(auction.release_bond(auction_id, accounts.bob));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 45);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 0);
+        }
+
+        // we test that buying a bonded lot now awaits confirmation like ending it does
+        #[test]
+        fn bonded_buy_now_awaits_confirmation() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_bid_bond(auction_id, 5, 0));
+            assert!(auction.set_buy_now_price(auction_id, 50, 5_000));
+
+            set_sender_and_value(accounts.bob, 5);
+            assert!(auction.register_bond(auction_id).is_ok());
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.charlie, 5);
+            assert!(auction.register_bond(auction_id).is_ok());
+            set_sender_and_value(accounts.charlie, 50);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(auction.is_ended(auction_id));
+            assert!(auction.is_awaiting_confirmation(auction_id));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 0);
+            assert!(!auction.release_bond(auction_id, accounts.charlie));
+
+            // charlie lets the deadline pass, the lot is offered to bob at the bid bob made
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(auction.forfeit_bond(auction_id));
+            assert_eq!(auction.get_highest_bidder(auction_id), accounts.bob);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 50);
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.confirm_purchase(auction_id).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 5);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 15);
+        }
+
+        // we test that escrowed lots hold the price until delivery, the timeout or the arbitrator's ruling
+        #[test]
+        fn escrow_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+
+            // the winner confirms delivery
+            let auction_id = auction.create_auction(0, 60);
+            assert!(!auction.set_escrow(auction_id, accounts.alice, 0, 0, 5000));
+            assert!(auction.set_escrow(auction_id, accounts.eve, 0, 0, 5000));
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert_eq!(auction.get_escrow_state(auction_id), Some(EscrowState::AwaitingDelivery));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 0);
+            assert!(!auction.confirm_delivery(auction_id));
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.confirm_delivery(auction_id));
+            assert_eq!(auction.get_escrow_state(auction_id), Some(EscrowState::Released));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 10);
+
+            // the beneficiary disputes and the arbitrator gives a quarter back
+            set_sender_and_value(accounts.alice, 0);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_escrow(auction_id, accounts.eve, 0, 0, 5000));
+            set_sender_and_value(accounts.bob, 20);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            set_sender_and_value(accounts.charlie, 0);
+            assert!(!auction.raise_dispute(auction_id));
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.raise_dispute(auction_id));
+            assert!(!auction.resolve_dispute(auction_id, 2500));
+            set_sender_and_value(accounts.bob, 0);
+            assert!(!auction.confirm_delivery(auction_id));
+            set_sender_and_value(accounts.eve, 0);
+            assert!(auction.resolve_dispute(auction_id, 2500));
+            assert_eq!(auction.get_escrow_state(auction_id), Some(EscrowState::Resolved));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 5);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 25);
+
+            // nobody confirms, the price is released after the timeout
+            set_sender_and_value(accounts.alice, 0);
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_escrow(auction_id, accounts.eve, 0, 0, 5000));
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            assert!(!auction.release_escrow(auction_id));
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(!auction.raise_dispute(auction_id));
+            assert!(auction.release_escrow(auction_id));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 35);
+
+            // the arbitrator never rules, the dispute is resolved by default after the dispute timeout
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_escrow(auction_id, accounts.eve, 0, 0, 5000));
+            set_sender_and_value(accounts.bob, 20);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.raise_dispute(auction_id));
+            assert!(!auction.resolve_by_default(auction_id));
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(auction.resolve_by_default(auction_id));
+            assert_eq!(auction.get_escrow_state(auction_id), Some(EscrowState::Resolved));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 15);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 45);
+        }
+
+        // we test that the beneficiary can be handed over and the lot described until the first bid
+        #[test]
+        fn beneficiary_and_metadata_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_for(accounts.bob, 0, 60, 1, accounts.bob, 0);
+            assert_eq!(auction.get_beneficiary(0), accounts.bob);
+            assert!(!auction.set_metadata(0, String::from("Vase"), String::from("bafy"), String::from("art")));
+
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.set_metadata(0, String::from("Vase"), String::from("bafy"), String::from("art")));
+            assert!(auction.propose_beneficiary(0, accounts.charlie));
+            assert!(!auction.accept_beneficiary(0));
+            set_sender_and_value(accounts.charlie, 0);
+            assert!(auction.accept_beneficiary(0));
+            assert_eq!(auction.get_beneficiary(0), accounts.charlie);
+
+            set_sender_and_value(accounts.django, 10);
+            assert!(auction.bid(0).is_ok());
+            set_sender_and_value(accounts.charlie, 0);
+            assert!(!auction.set_metadata(0, String::from("Urn"), String::from("bafy"), String::from("art")));
+
+            let info = auction.get_info(0).expect("lot 0 exists");
+            assert_eq!(info.beneficiary, accounts.charlie);
+            assert_eq!(info.proposed_beneficiary, None);
+            assert_eq!(info.metadata.title, String::from("Vase"));
+            assert_eq!(info.metadata.content_hash, String::from("bafy"));
+            assert_eq!(info.highest_bid, 10);
+            assert_eq!(auction.get_info(1), None);
+        }
+
+        // we test that a house measuring time in blocks ends its lots after a number of blocks
+        #[test]
+        fn block_deadlines_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_in_blocks(0, 2);
+            assert_eq!(auction.get_time_unit(), TimeUnit::Blocks);
+            assert_eq!(auction.get_time_left(0), 2);
+
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(0).is_ok());
+            assert_eq!(auction.end(0), Err(AuctionError::EndTooEarly));
+
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert_eq!(auction.get_time_left(0), 1);
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert_eq!(auction.get_time_left(0), 0);
+            assert_eq!(auction.end(0), Err(AuctionError::EndTooEarly));
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(auction.end(0).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 10);
+        }
+
+        // we test that a lot can be opened on someone else's behalf
+        #[test]
+        fn new_for_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_for(accounts.bob, 5, 60, 2, accounts.django, 100);
+            assert_eq!(auction.get_beneficiary(0), accounts.bob);
+            assert_eq!(auction.get_min_increment(), Increment::Absolute(2));
+            assert_eq!(auction.get_platform_fee(), Some(Split { recipient: accounts.django, basis_points: 100 }));
+
+            set_sender_and_value(accounts.charlie, 100);
+            assert!(auction.bid(0).is_ok());
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.end(0).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 99);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.django), 1);
+        }
+
+        // we test that the proceeds are split between the fee, the royalties and the beneficiary
+        #[test]
+        fn payout_splits_work() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let fee = Split { recipient: accounts.django, basis_points: 250 };
+            let royalties = vec![
+                Split { recipient: accounts.eve, basis_points: 1000 },
+                Split { recipient: accounts.frank, basis_points: 333 },
+            ];
+            let mut auction = Auction::new_marketplace(Increment::Absolute(1), None, Some(fee), royalties.clone(),
+                                                   RejectionPolicy::CreditPending);
+            assert_eq!(auction.get_platform_fee(), Some(fee));
+            assert_eq!(auction.get_royalties(), royalties);
+
+            let auction_id = auction.create_auction(0, 60);
+            set_sender_and_value(accounts.bob, 101);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_ok());
+
+            assert_eq!(auction.curr_withdrawl_amount(accounts.django), 2);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.eve), 10);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.frank), 3);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 86);
+        }
+
+        // we test that restricted lots only take bids from the allowlist or proven Merkle leaves
+        #[test]
+        fn eligibility_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            assert_eq!(auction.get_eligibility(auction_id), Eligibility::Open);
+            assert!(auction.set_eligibility(auction_id, Eligibility::Allowlist));
+            assert!(auction.add_to_allowlist(auction_id, vec![accounts.bob]));
+
+            set_sender_and_value(accounts.charlie, 10);
+            assert!(auction.bid(auction_id).is_err());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 10);
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+
+            // the mode is locked once the lot is bid on
+            set_sender_and_value(accounts.alice, 0);
+            assert!(!auction.set_eligibility(auction_id, Eligibility::Open));
+
+            // a two leaf tree of bob and charlie
+            let leaf = |account: AccountId| Hash::from(scale::Encode::using_encoded(&account, ink_core::hash::blake2_256));
+            let root = auction.hash_pair(leaf(accounts.bob), leaf(accounts.charlie));
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_eligibility(auction_id, Eligibility::MerkleRoot(root)));
+
+            set_sender_and_value(accounts.charlie, 0);
+            assert!(!auction.prove_eligibility(auction_id, vec![leaf(accounts.django)]));
+            assert!(!auction.is_eligible(auction_id, accounts.charlie));
+            assert!(auction.prove_eligibility(auction_id, vec![leaf(accounts.bob)]));
+            assert!(auction.is_eligible(auction_id, accounts.charlie));
+            set_sender_and_value(accounts.charlie, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.bob, 20);
+            assert!(auction.bid(auction_id).is_err());
+        }
+
+        // we test that multi-unit lots sell to the top unit prices at the lowest winning one, in batches
+        #[test]
+        fn multi_unit_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_multi_unit_auction(1, 5, 60);
+
+            set_sender_and_value(accounts.bob, 30);
+            assert!(auction.bid_units(auction_id, 3, None).is_ok());
+            set_sender_and_value(accounts.charlie, 24);
+            assert!(auction.bid_units(auction_id, 2, None).is_ok());
+            // a stale hint still finds the right place
+            set_sender_and_value(accounts.django, 21);
+            assert!(auction.bid_units(auction_id, 3, Some(1)).is_ok());
+            set_sender_and_value(accounts.eve, 2);
+            assert!(auction.bid_units(auction_id, 2, None).is_err());
+            set_sender_and_value(accounts.eve, 60);
+            assert!(auction.bid_units(auction_id, 6, None).is_err());
+            assert_eq!(auction.get_unit_bid_count(auction_id), 3);
+            assert_eq!(auction.get_highest_bid(auction_id), 12);
+            assert_eq!(auction.find_unit_bid_position(auction_id, 11, None), Some(1));
+
+            set_sender_and_value(accounts.alice, 0);
+            assert!(!auction.settle_units(auction_id, 10));
+            assert!(auction.end(auction_id).is_ok());
+            let mut batches = 0;
+            while !auction.settle_units(auction_id, 1) {
+                batches += 1;
+            }
+            assert!(batches > 3);
+
+            // charlie's 2 units at 12 and 3 of bob's at 10 clear at 10, django's 7s lose
+            assert_eq!(auction.get_unit_settlement(auction_id).clearing_price, 10);
+            assert_eq!(auction.get_units_won(auction_id, accounts.charlie), 2);
+            assert_eq!(auction.get_units_won(auction_id, accounts.bob), 3);
+            assert_eq!(auction.get_units_won(auction_id, accounts.django), 0);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 4);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.django), 21);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 50);
+            assert!(auction.settle_units(auction_id, 1));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 50);
+        }
+
+        // we test that a candle lot goes to the leader as of the drawn close
+        #[test]
+        fn candle_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            env::test::set_block_entropy::<EnvTypes>(Hash::from([7; 32])).expect("Cannot set entropy");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_candle_auction(0, 1000, 500, 10);
+            assert_eq!(auction.get_candle_slots(auction_id), 50);
+
+            // bob leads before the ending period, charlie during it
+            assert_eq!(auction.get_candle_slot(auction_id), 0);
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            while auction.get_candle_slot(auction_id) == 0 {
+                env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            }
+            let charlie_slot = auction.get_candle_slot(auction_id);
+            set_sender_and_value(accounts.charlie, 20);
+            assert!(auction.bid(auction_id).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+
+            // even the beneficiary has to wait for the ending period, bids after it are refused
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.end(auction_id).is_err());
+            while !auction.time_end_allowed(auction_id) {
+                env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            }
+            set_sender_and_value(accounts.django, 50);
+            assert!(auction.bid(auction_id).is_err());
+            assert!(auction.end(auction_id).is_ok());
+
+            // the close is drawn in the block after the one that ended the lot, a missed draw moves on
+            assert_eq!(auction.get_candle_close(auction_id), None);
+            assert!(!auction.release_candle_bid(auction_id, accounts.bob));
+            assert!(!auction.draw_candle(auction_id));
+            let end_block = auction.get_candle_end_block(auction_id).expect("Lot is ended");
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(!auction.draw_candle(auction_id));
+            assert_eq!(auction.get_candle_end_block(auction_id), Some(end_block + 2));
+            assert_eq!(auction.get_candle_close(auction_id), None);
+            env::test::advance_block::<EnvTypes>().expect("Cannot advance block");
+            assert!(auction.draw_candle(auction_id));
+            assert!(!auction.draw_candle(auction_id));
+
+            let close = auction.get_candle_close(auction_id).expect("Close is drawn at settlement");
+            assert!(close >= 1 && close <= 50);
+            let (winner, loser, winning_bid, losing_bid) = if close >= charlie_slot {
+                (accounts.charlie, accounts.bob, 20, 10)
+            } else {
+                (accounts.bob, accounts.charlie, 10, 20)
+            };
+            assert_eq!(auction.get_highest_bidder(auction_id), winner);
+            assert!(!auction.release_candle_bid(auction_id, winner));
+            assert!(auction.release_candle_bid(auction_id, loser));
+            assert_eq!(auction.curr_withdrawl_amount(loser), losing_bid);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), winning_bid);
+        }
+
+        // we test that the bids on a cancelled candle lot can be released and withdrawn without a close
+        #[test]
+        fn cancelled_candle_bids_are_released() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_candle_auction(0, 1000, 500, 10);
+            assert!(auction.set_cancel_policy(auction_id, CancelPolicy::WithPenalty(1000)));
+            transfer_in(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_ok());
+            transfer_in(accounts.charlie, 20);
+            assert!(auction.bid(auction_id).is_ok());
+
+            transfer_in(accounts.alice, 2);
+            assert!(auction.cancel(auction_id));
+            assert!(!auction.draw_candle(auction_id));
+            assert_eq!(auction.get_candle_close(auction_id), None);
+
+            // the penalty goes to the highest bidder, every bid comes back in full
+            assert!(auction.release_candle_bid(auction_id, accounts.bob));
+            assert!(auction.release_candle_bid(auction_id, accounts.charlie));
+            assert!(!auction.release_candle_bid(auction_id, accounts.bob));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 10);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 22);
+            set_sender_and_value(accounts.bob, 0);
+            assert!(auction.withdraw().is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.bob), 0);
+        }
+
+        // we test that a lot can only be cancelled as its policy allows and that bids are returned
+        #[test]
+        fn cancel_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+
+            // without bids the lot can be cancelled under the default policy
+            let auction_id = auction.create_auction(0, 60);
+            assert_eq!(auction.get_cancel_policy(auction_id), CancelPolicy::BeforeBids);
+            set_sender_and_value(accounts.bob, 0);
+            assert!(!auction.cancel(auction_id));
+            set_sender_and_value(accounts.alice, 0);
+            assert!(auction.cancel(auction_id));
+            assert!(auction.is_cancelled(auction_id));
+            assert!(auction.end(auction_id).is_err());
+            set_sender_and_value(accounts.bob, 10);
+            assert!(auction.bid(auction_id).is_err());
+
+            // once bid on it can't
+            set_sender_and_value(accounts.alice, 0);
+            let auction_id = auction.create_auction(0, 60);
+            set_sender_and_value(accounts.bob, 20);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 0);
+            assert!(!auction.cancel(auction_id));
+
+            // unless the beneficiary pays the penalty
+            let auction_id = auction.create_auction(0, 60);
+            assert!(auction.set_cancel_policy(auction_id, CancelPolicy::WithPenalty(1000)));
+            set_sender_and_value(accounts.charlie, 50);
+            assert!(auction.bid(auction_id).is_ok());
+            set_sender_and_value(accounts.alice, 4);
+            assert!(!auction.cancel(auction_id));
+            set_sender_and_value(accounts.alice, 1);
+            assert!(auction.cancel(auction_id));
+            assert_eq!(auction.curr_withdrawl_amount(accounts.charlie), 55);
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 0);
+            assert!(auction.end(auction_id).is_err());
+        }
+
+        // we test that a hidden reserve only counts once it is revealed
+        #[test]
+        fn hidden_reserve_works() {
+            let accounts = env::test::default_accounts::<EnvTypes>().expect("Cannot get accounts");
+            let mut auction = Auction::new_house(Increment::Absolute(1), None);
+            let auction_id = auction.create_auction(0, 60);
+            let salt = Hash::from([0x3; 32]);
+            assert!(auction.commit_reserve_price(auction_id, auction.compute_commitment(30, salt)));
+
+            set_sender_and_value(accounts.bob, 40);
+            assert!(auction.bid(auction_id).is_ok());
+            assert!(!auction.is_reserve_met(auction_id));
+
+            set_sender_and_value(accounts.alice, 0);
+            assert!(!auction.reveal_reserve_price(auction_id, 20, salt));
+            assert!(auction.reveal_reserve_price(auction_id, 30, salt));
+            assert!(auction.is_reserve_met(auction_id));
+            assert!(auction.end(auction_id).is_ok());
+            assert_eq!(auction.curr_withdrawl_amount(accounts.alice), 40);
         }
 
     }